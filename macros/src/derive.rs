@@ -1,46 +1,203 @@
+mod bound;
 mod context;
 pub mod field;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use proc_macro2::TokenStream;
-use quote::{quote, quote_spanned};
-use serde_derive_internals::attr::RenameRule;
+use quote::{format_ident, quote, quote_spanned};
 use syn::{
-    parse_quote, DataEnum, DataStruct, DeriveInput, Fields, FieldsNamed, GenericParam, Generics,
-    Ident, ItemImpl,
+    parse_quote, DataEnum, DataStruct, DeriveInput, Fields, FieldsNamed, Generics, Ident, ItemImpl,
+    Path, Type,
 };
 
 use crate::{derive::field::Field, iter_ext::IterExt};
 
-use self::context::Container;
+use self::context::{constraints, Container, Deprecation, MetadataMerge, VariantCtx};
 
-pub fn derive(input: DeriveInput) -> Result<ItemImpl, syn::Error> {
+pub fn derive(input: DeriveInput) -> Result<TokenStream, syn::Error> {
+    // `#[cfg]` and `#[cfg_attr]` are resolved before derive macros run, so
+    // disabled fields and variants are already gone and any attributes
+    // enabled through `cfg_attr` look just like plain ones here.
     let ctx = context::Container::from_input(&input)?;
+    let mut errors = Errors::default();
+    if ctx.strict {
+        errors.take(context::lint::check_serde_attrs(&input));
+    }
 
-    let ident = input.ident;
+    let registration = gen_registration(&input);
+    let item_impl = errors.take(derive_impl(&ctx, input.clone()));
 
-    let (impl_generics_no_infer, ty_generics, where_clause) = input.generics.split_for_impl();
-    let mut impl_generics: Generics = parse_quote! {#impl_generics_no_infer};
-    for param in impl_generics.params.iter_mut() {
-        if let GenericParam::Type(ty) = param {
-            // We add the `JsonTypedef` bound to every type parameter.
-            // This isn't always correct, but it's an okay-ish heuristic.
-            ty.bounds.push(parse_quote! { ::jtd_derive::JsonTypedef });
+    // these look at the same fields and variants as the impl, so they'd only
+    // repeat its errors
+    let (key_impl, variant_tags) = if item_impl.is_some() {
+        let key_impl = errors.take(gen_key_impl(&ctx, &input)).flatten();
+        let variant_tags = if ctx.variant_tags {
+            errors.take(gen_variant_tags(&ctx, &input))
+        } else {
+            None
+        };
+        (key_impl, variant_tags)
+    } else {
+        (None, None)
+    };
+    errors.finish()?;
+
+    Ok(quote! {
+        #item_impl
+        #key_impl
+        #variant_tags
+        #registration
+    })
+}
+
+/// Implement `JsonTypedefKey` for types that serialize as strings. Enums with
+/// only unit variants do (unless they're internally tagged), so they get it
+/// automatically. Newtypes get it with `#[typedef(key)]`, as long as the inner
+/// type is a key itself.
+fn gen_key_impl(ctx: &Container, input: &DeriveInput) -> Result<Option<TokenStream>, syn::Error> {
+    let ident = &input.ident;
+    let generics = bounded_generics(&input.generics, &bound::schema_params(ctx, input)?);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let inner_ty = match (&input.data, &ctx.type_from, &ctx.type_try_from) {
+        (_, Some(ty), _) | (_, _, Some(ty)) => Some(ty),
+        (syn::Data::Enum(enu), _, _) => {
+            let is_string = !enu.variants.is_empty()
+                && enu
+                    .variants
+                    .iter()
+                    .all(|v| matches!(v.fields, Fields::Unit))
+                && matches!(ctx.tag_type, context::TagType::External);
+
+            if is_string && (ctx.key || input.generics.params.is_empty()) {
+                return Ok(Some(quote! {
+                    impl #impl_generics ::jtd_derive::JsonTypedefKey for #ident #ty_generics #where_clause {}
+                }));
+            }
+            None
         }
+        (syn::Data::Struct(s), _, _) => match &s.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => Some(&fields.unnamed[0].ty),
+            Fields::Named(fields) if fields.named.len() == 1 && ctx.transparent => {
+                Some(&fields.named[0].ty)
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+
+    if !ctx.key {
+        return Ok(None);
     }
 
-    let type_params = input.generics.type_params().map(|p| &p.ident);
+    let Some(inner_ty) = inner_ty else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(key)] is only allowed on newtypes and enums with unit variants",
+        ));
+    };
+
+    let mut where_clause = where_clause
+        .cloned()
+        .unwrap_or_else(|| parse_quote! { where });
+    where_clause
+        .predicates
+        .push(parse_quote! { #inner_ty: ::jtd_derive::JsonTypedefKey });
+
+    Ok(Some(quote! {
+        impl #impl_generics ::jtd_derive::JsonTypedefKey for #ident #ty_generics #where_clause {}
+    }))
+}
+
+/// The generics of the derived impls. We add the `JsonTypedef` bound to the
+/// type parameters the schema depends on. See [`bound::schema_params`].
+///
+/// The bounds go in the where-clause, so that they don't get in the way of
+/// whatever the parameters are already declared with.
+fn bounded_generics(generics: &Generics, bounded: &HashSet<Ident>) -> Generics {
+    let mut generics = generics.clone();
+    let params: Vec<_> = generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .filter(|param| bounded.contains(param))
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(parse_quote! { #param: ::jtd_derive::JsonTypedef });
+    }
+    generics
+}
+
+/// With the `inventory` feature, non-generic types are submitted to the
+/// global registry so that `jtd_derive::registry::all_types()` can find them.
+/// Generic types can't be registered since we don't know their parameters.
+#[cfg(feature = "inventory")]
+fn gen_registration(input: &DeriveInput) -> Option<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return None;
+    }
+
+    let ident = &input.ident;
+
+    Some(quote! {
+        #[allow(deprecated)]
+        const _: () = {
+            ::jtd_derive::register_type!(#ident);
+        };
+    })
+}
+
+#[cfg(not(feature = "inventory"))]
+fn gen_registration(_input: &DeriveInput) -> Option<TokenStream> {
+    None
+}
+
+fn derive_impl(ctx: &Container, input: DeriveInput) -> Result<ItemImpl, syn::Error> {
+    let schema_params = bound::schema_params(ctx, &input)?;
+    let ident = input.ident;
+
+    let (impl_generics_no_infer, ty_generics, where_clause) = input.generics.split_for_impl();
+    let bounded = bounded_generics(&input.generics, &schema_params);
+    let (impl_generics, _, bounded_where_clause) = bounded.split_for_impl();
+
+    // parameters the schema doesn't depend on aren't `JsonTypedef`, but they
+    // still tell instantiations apart
+    let type_params = input.generics.type_params().map(|p| {
+        let param = &p.ident;
+        if schema_params.contains(param) {
+            quote! { #param::names() }
+        } else {
+            quote! { ::jtd_derive::__private::type_param_names::<#param>() }
+        }
+    });
     let const_params = input.generics.const_params().map(|p| &p.ident);
 
+    let name = ctx.rename.clone().unwrap_or_else(|| ident.to_string());
     let names_impl = quote! {
         fn names() -> ::jtd_derive::Names {
             ::jtd_derive::Names {
-                short: stringify!(#ident),
-                long: concat!(module_path!(), "::", stringify!(#ident)),
+                short: #name,
+                long: concat!(module_path!(), "::", #name),
+                path_segments: module_path!()
+                    .split("::")
+                    .chain([#name])
+                    .collect(),
                 nullable: false,
-                type_params: [#(#type_params::names()),*].into(),
-                const_params: [#(#const_params.to_string()),*].into(),
+                type_params: [#(#type_params),*].into(),
+                const_params: [#(::jtd_derive::__private::const_param_name(#const_params)),*].into(),
+            }
+        }
+    };
+
+    let source_location_impl = quote_spanned! {ident.span()=>
+        fn source_location() -> ::std::option::Option<(&'static str, u32)> {
+            if cfg!(debug_assertions) {
+                ::std::option::Option::Some((file!(), line!()))
+            } else {
+                ::std::option::Option::None
             }
         }
     };
@@ -76,6 +233,8 @@ pub fn derive(input: DeriveInput) -> Result<ItemImpl, syn::Error> {
                     }
 
                     #names_impl
+
+                    #source_location_impl
                 }
             });
         }
@@ -88,25 +247,24 @@ pub fn derive(input: DeriveInput) -> Result<ItemImpl, syn::Error> {
     }
 
     let res = match input.data {
-        syn::Data::Struct(s) => gen_struct_schema(&ctx, &ident, s)?,
-        syn::Data::Enum(e) => gen_enum_schema(&ctx, &ident, e)?,
+        syn::Data::Struct(s) => gen_struct_schema(ctx, &ident, s)?,
+        syn::Data::Enum(e) => gen_enum_schema(ctx, &ident, e)?,
         syn::Data::Union(_) => {
             quote_spanned! {ident.span()=> compile_error!("jtd-derive does not support unions")}
         }
     };
-    let meta = gen_metadata(&ctx.metadata);
-
-    let res = quote! { {
-        let mut schema = #res;
-        schema.metadata.extend(#meta);
-        schema
-    } };
+    let res = with_description(res, &ctx.doc);
+    let res = with_metadata(res, &ctx.metadata);
+    let res = with_metadata_with(res, &ctx.metadata_with);
+    let res = with_fuzz_hint(res, &ctx.fuzz_hint);
+    let res = with_deprecation(res, &ctx.deprecated);
 
     Ok(parse_quote! {
-        impl #impl_generics ::jtd_derive::JsonTypedef for #ident #ty_generics #where_clause {
+        // the type, its fields or variants might be `#[deprecated]`
+        #[allow(deprecated)]
+        impl #impl_generics ::jtd_derive::JsonTypedef for #ident #ty_generics #bounded_where_clause {
             fn schema(gen: &mut ::jtd_derive::Generator) -> ::jtd_derive::schema::Schema {
                 use ::jtd_derive::JsonTypedef;
-                use ::jtd_derive::schema::{Schema, SchemaType};
                 #res
             }
 
@@ -115,6 +273,38 @@ pub fn derive(input: DeriveInput) -> Result<ItemImpl, syn::Error> {
             }
 
             #names_impl
+
+            #source_location_impl
+        }
+    })
+}
+
+/// Expose the discriminator and variant tags of a tagged enum, so that code
+/// can use exactly the values the schema advertises.
+fn gen_variant_tags(ctx: &Container, input: &DeriveInput) -> Result<TokenStream, syn::Error> {
+    let ident = &input.ident;
+    let (syn::Data::Enum(enu), context::TagType::Internal(tag)) = (&input.data, &ctx.tag_type)
+    else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(variant_tags)] is only allowed on tagged enums",
+        ));
+    };
+
+    // these can't depend on the schema mode, so they're the names variants are
+    // deserialized from
+    let names = variant_names(ctx, enu, Direction::Deserialize)?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// The name of the property holding the variant tag.
+            pub const DISCRIMINATOR: &'static str = #tag;
+
+            /// The tags of all the variants, in declaration order.
+            pub fn variant_tags() -> &'static [&'static str] {
+                &[#(#names),*]
+            }
         }
     })
 }
@@ -124,6 +314,27 @@ fn gen_struct_schema(
     ident: &Ident,
     s: DataStruct,
 ) -> Result<TokenStream, syn::Error> {
+    if ctx.discriminants {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(discriminants)] is only allowed on enums with unit variants",
+        ));
+    }
+
+    if let Some(values) = &ctx.values {
+        if ctx.transparent {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "can't set both `#[typedef(values = \"...\")]` and `#[typedef(transparent)]`",
+            ));
+        }
+
+        // the fields don't matter, the struct is (de)serialized as a map
+        return Ok(quote! {
+            ::jtd_derive::schema::Schema::values(gen.sub_schema::<#values>())
+        });
+    }
+
     match s.fields {
         Fields::Named(_) if s.fields.is_empty() => Err(syn::Error::new_spanned(
             ident,
@@ -144,15 +355,28 @@ fn gen_struct_schema(
                 ))
                 //}
             } else {
-                gen_named_fields(ctx, &fields, ctx.rename_rule)
+                gen_named_fields(ctx, &fields, true, ctx.deny_unknown_fields, None)
             }
         }
         Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
-            let ty = &fields.unnamed[0].ty;
+            let field = &fields.unnamed[0];
+            let ty = &field.ty;
+            let mut errors = Errors::default();
+            let field_constraints: Vec<_> = [
+                errors.take(constraints::from_serde_valid(&field.attrs)),
+                errors.take(constraints::from_validator(&field.attrs, ty)),
+                errors.take(constraints::from_garde(&field.attrs, ty)),
+            ]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect();
+            errors.finish()?;
 
-            Ok(parse_quote! {
-                gen.sub_schema::<#ty>()
-            })
+            Ok(constraints::apply(
+                quote! { gen.sub_schema::<#ty>() },
+                &field_constraints,
+            ))
         }
         Fields::Unnamed(_) => Err(syn::Error::new_spanned(
             ident,
@@ -184,134 +408,651 @@ fn gen_enum_schema(
         ));
     }
 
+    if ctx.values.is_some() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "#[typedef(values = \"...\")] is not allowed on an enum",
+        ));
+    }
+
+    // everything below looks at variant attributes, so check them all first
+    enu.variants
+        .iter()
+        .map(VariantCtx::from_input)
+        .collect_fallible::<Vec<_>>()?;
+
     match enum_kind(ident, &enu)? {
         EnumKind::UnitVariants => {
-            let mut idents: Vec<_> = enu.variants.iter().map(|v| v.ident.to_string()).collect();
-            if let Some(rule) = ctx.rename_rule {
-                for ident in idents.iter_mut() {
-                    *ident = rule.apply_to_variant(ident);
-                }
-            }
+            let idents = gen_variant_names(ctx, &enu)?;
 
-            let enum_schema = parse_quote! {
-                Schema {
-                    ty: SchemaType::Enum {
-                        r#enum: [#(#idents),*].into(),
-                    },
-                    ..::jtd_derive::schema::Schema::default()
+            // explicit discriminants are usually there for a reason, so we keep
+            // them without being asked to
+            let explicit_discriminants = enu.variants.iter().any(|v| v.discriminant.is_some());
+            let variant_idents = enu.variants.iter().map(|v| &v.ident);
+            let discriminants = (ctx.discriminants || explicit_discriminants).then(|| {
+                quote! {
+                    .with_metadata(
+                        "discriminants",
+                        ::jtd_derive::__private::serde_json::json!({
+                            #(#idents: Self::#variant_idents as i128),*
+                        }),
+                    )
                 }
+            });
+            let catch_all = gen_catch_all(&enu, &idents)?;
+            let deprecated = gen_deprecated_variants(&enu, &idents)?;
+            let enum_schema = match &ctx.repr {
+                // the enum is (de)serialized as its discriminant
+                Some(repr) => {
+                    let ty = format_ident!("{}", repr_type_schema(repr));
+                    let variant_idents = enu.variants.iter().map(|v| &v.ident);
+                    quote! {
+                        ::jtd_derive::schema::Schema::of_type(::jtd_derive::schema::TypeSchema::#ty)
+                            .with_metadata(
+                                "allowedValues",
+                                ::jtd_derive::__private::serde_json::json!([
+                                    #(Self::#variant_idents as i128),*
+                                ]),
+                            )
+                            #discriminants #catch_all #deprecated
+                    }
+                }
+                None => quote! {
+                    ::jtd_derive::schema::Schema::enumeration([#(#idents),*])
+                        #discriminants #catch_all #deprecated
+                },
             };
 
             match &ctx.tag_type {
                 context::TagType::External => Ok(enum_schema),
-                context::TagType::Internal(tag) => Ok(parse_quote! {
-                    Schema {
-                        ty: SchemaType::Properties {
-                            properties: [
-                                (#tag, #enum_schema)
-                            ].into(),
-                            additional_properties: true,
-                            optional_properties: [].into(),
-                        },
-                        ..::jtd_derive::schema::Schema::default()
-                    }
+                context::TagType::Internal(tag) => Ok(quote! {
+                    ::jtd_derive::schema::Schema::properties()
+                        .property(#tag, #enum_schema)
+                        .additional_properties(true)
+                        .build()
                 }),
             }
         }
         EnumKind::StructVariants => {
-            let tag = match &ctx.tag_type {
-                context::TagType::External => {
-                    return Err(syn::Error::new_spanned(
-                        ident,
-                        "jtd-derive requires an enum with struct variants to have a tag",
-                    ));
-                }
-                context::TagType::Internal(t) => t,
+            let mut errors = Errors::default();
+            if ctx.discriminants {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    "#[typedef(discriminants)] is only allowed on enums with unit variants",
+                ));
+            }
+            if ctx.repr.is_some() {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    "#[typedef(repr = \"...\")] is only allowed on enums with unit variants",
+                ));
+            }
+            if let context::TagType::External = &ctx.tag_type {
+                errors.push(syn::Error::new_spanned(
+                    ident,
+                    "jtd-derive requires an enum with struct variants to have a tag",
+                ));
+            }
+
+            let variants: Option<Vec<_>> = errors.take(
+                enu.variants
+                    .iter()
+                    .map(|v| {
+                        let variant = VariantCtx::from_input(v)?;
+                        let tag = match &ctx.tag_type {
+                            context::TagType::Internal(tag) => Some(tag.as_str()),
+                            context::TagType::External => None,
+                        };
+                        let deny_unknown_fields = variant
+                            .deny_unknown_fields
+                            .unwrap_or(ctx.deny_unknown_fields);
+                        let schema = match &v.fields {
+                            // only `#[serde(other)]` variants get here
+                            Fields::Unit => gen_unit_variant(deny_unknown_fields),
+                            Fields::Unnamed(fields) => {
+                                gen_newtype_variant(&fields.unnamed[0].ty, deny_unknown_fields)
+                            }
+                            fields => gen_named_fields(
+                                ctx,
+                                unwrap_fields_named(fields),
+                                false,
+                                deny_unknown_fields,
+                                Some(&v.ident).zip(tag),
+                            )?,
+                        };
+                        Ok(with_deprecation(schema, &variant.deprecated))
+                    })
+                    .collect_fallible(),
+            );
+            let idents = errors.take(gen_variant_names(ctx, &enu));
+            let catch_all = idents
+                .as_ref()
+                .and_then(|idents| errors.take(gen_catch_all(&enu, idents)));
+            errors.finish()?;
+
+            // nothing above failed, so all of these are there
+            let (context::TagType::Internal(tag), Some(variants), Some(idents), Some(catch_all)) =
+                (&ctx.tag_type, variants, idents, catch_all)
+            else {
+                unreachable!()
             };
 
-            let (mut idents, variants): (Vec<_>, Vec<_>) = enu
-                .variants
-                .iter()
-                .map(|v| {
-                    (
-                        v.ident.to_string(),
-                        gen_named_fields(ctx, unwrap_fields_named(&v.fields), None),
-                    )
-                })
-                .unzip();
-            let variants: Vec<_> = variants.into_iter().collect_fallible()?;
-            if let Some(rule) = ctx.rename_rule {
-                for ident in idents.iter_mut() {
-                    *ident = rule.apply_to_variant(ident);
-                }
+            Ok(quote! {
+                ::jtd_derive::schema::Schema::discriminator(#tag, [#((#idents, #variants)),*])
+                    #catch_all
+            })
+        }
+    }
+}
+
+/// The `TypeSchema` variant for a `#[typedef(repr = "...")]` value.
+fn repr_type_schema(repr: &str) -> &'static str {
+    match repr {
+        "int8" => "Int8",
+        "uint8" => "Uint8",
+        "int16" => "Int16",
+        "uint16" => "Uint16",
+        "int32" => "Int32",
+        "uint32" => "Uint32",
+        _ => unreachable!("validated when parsing"),
+    }
+}
+
+/// Typedef has no way to express "any other value", so a variant marked with
+/// `#[typedef(catch_all)]` is only named in the `catchAll` metadata entry.
+fn gen_catch_all(enu: &DataEnum, names: &[TokenStream]) -> Result<Option<TokenStream>, syn::Error> {
+    let ctxs: Vec<_> = enu
+        .variants
+        .iter()
+        .map(VariantCtx::from_input)
+        .collect_fallible()?;
+    let mut catch_all = enu
+        .variants
+        .iter()
+        .zip(names)
+        .zip(ctxs)
+        .filter_map(|(variant, ctx)| ctx.catch_all.then_some(variant));
+
+    let Some((first, name)) = catch_all.next() else {
+        return Ok(None);
+    };
+    if let Some((second, _)) = catch_all.next() {
+        let mut err = syn::Error::new_spanned(
+            &second.ident,
+            "only one variant can be marked with #[typedef(catch_all)]",
+        );
+        err.combine(syn::Error::new_spanned(
+            &first.ident,
+            format!("`{}` is already marked here", first.ident),
+        ));
+        return Err(err);
+    }
+
+    Ok(Some(quote! { .with_metadata("catchAll", #name) }))
+}
+
+/// List the names of `#[deprecated]` unit variants, since an enum schema has
+/// nowhere else to say it.
+fn gen_deprecated_variants(
+    enu: &DataEnum,
+    names: &[TokenStream],
+) -> Result<Option<TokenStream>, syn::Error> {
+    let ctxs: Vec<_> = enu
+        .variants
+        .iter()
+        .map(VariantCtx::from_input)
+        .collect_fallible()?;
+    let deprecated: Vec<_> = names
+        .iter()
+        .zip(ctxs)
+        .filter_map(|(name, ctx)| ctx.deprecated.is_some().then_some(name))
+        .collect();
+
+    if deprecated.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(quote! {
+        .with_metadata(
+            "deprecatedVariants",
+            ::jtd_derive::__private::serde_json::json!([#(#deprecated),*]),
+        )
+    }))
+}
+
+/// Like [`check_unique_variant_names`], but for property keys.
+fn check_unique_field_names<'a>(
+    fields: impl Iterator<Item = (&'a syn::Field, &'a String)>,
+) -> Result<(), syn::Error> {
+    let mut seen = HashMap::new();
+
+    fields
+        .map(|(field, name)| match seen.insert(name, field) {
+            Some(first) => {
+                let mut err = syn::Error::new_spanned(
+                    &field.ident,
+                    format!(
+                        "fields `{}` and `{}` are both named \"{}\" in the schema",
+                        first.ident.as_ref().unwrap(),
+                        field.ident.as_ref().unwrap(),
+                        name
+                    ),
+                );
+                err.combine(syn::Error::new_spanned(
+                    &first.ident,
+                    format!(
+                        "`{}` is named \"{}\" here",
+                        first.ident.as_ref().unwrap(),
+                        name
+                    ),
+                ));
+                Err(err)
             }
+            None => Ok(()),
+        })
+        .collect_fallible()
+}
 
-            Ok(parse_quote! {
-                Schema {
-                    ty: SchemaType::Discriminator {
-                        discriminator: #tag,
-                        mapping: [#((#idents, #variants)),*].into(),
-                    },
-                    ..::jtd_derive::schema::Schema::default()
-                }
+/// Typedef doesn't allow the properties of a discriminator mapping to include
+/// the tag itself. Serde wouldn't be able to tell them apart either.
+fn check_tag_clash(
+    variant: &Ident,
+    tag: &str,
+    fields_named: &FieldsNamed,
+    fields: &[Field],
+    de_names: &[String],
+    ser_names: &[String],
+) -> Result<(), syn::Error> {
+    fields_named
+        .named
+        .iter()
+        .zip(fields)
+        .zip(de_names.iter().zip(ser_names))
+        .filter(|((_, f), (de, ser))| {
+            !f.flatten
+                && ((!f.skip_deserializing && *de == tag) || (!f.skip_serializing && *ser == tag))
+        })
+        .map(|((field, _), _)| {
+            Err(syn::Error::new_spanned(
+                &field.ident,
+                format!(
+                    "`{}` has a field named \"{}\" in the schema, which is already the tag of \
+                     the enum",
+                    variant, tag
+                ),
+            ))
+        })
+        .collect_fallible()
+}
+
+/// The names variants appear under in the schema, i.e. enum values or
+/// discriminator mapping keys.
+fn variant_names(
+    ctx: &Container,
+    enu: &DataEnum,
+    direction: Direction,
+) -> Result<Vec<String>, syn::Error> {
+    enu.variants
+        .iter()
+        .map(|v| {
+            let variant = VariantCtx::from_input(v)?;
+            let (rename, rule) = match direction {
+                Direction::Serialize => (variant.rename_serialize, ctx.rename_rule_serialize),
+                Direction::Deserialize => (variant.rename, ctx.rename_rule),
+            };
+            if let Some(rename) = rename {
+                return Ok(rename);
+            }
+
+            let name = v.ident.to_string();
+            Ok(match rule {
+                Some(rule) => rule.apply_to_variant(&name),
+                None => name,
             })
+        })
+        .collect_fallible()
+}
+
+/// Expressions evaluating to the variant names for the generator's
+/// [`SchemaMode`](jtd_derive::SchemaMode).
+fn gen_variant_names(ctx: &Container, enu: &DataEnum) -> Result<Vec<TokenStream>, syn::Error> {
+    let de = variant_names(ctx, enu, Direction::Deserialize)?;
+    check_unique_variant_names(enu, &de)?;
+    let ser = variant_names(ctx, enu, Direction::Serialize)?;
+    check_unique_variant_names(enu, &ser)?;
+
+    Ok(de
+        .iter()
+        .zip(&ser)
+        .map(|(de, ser)| by_mode(de, ser))
+        .collect())
+}
+
+/// Whether names are what gets serialized or what gets deserialized.
+#[derive(Clone, Copy)]
+enum Direction {
+    Serialize,
+    Deserialize,
+}
+
+/// A name that depends on the generator's schema mode. Most of the time
+/// both are the same and this is just a literal.
+fn by_mode(de: &str, ser: &str) -> TokenStream {
+    if de == ser {
+        quote! { #de }
+    } else {
+        quote! {
+            (if gen.mode() == ::jtd_derive::SchemaMode::Deserialize { #de } else { #ser })
         }
     }
 }
 
-fn gen_metadata(meta: &HashMap<String, String>) -> TokenStream {
+/// Typedef requires enum values and discriminator mapping keys to be unique.
+/// Renaming can make distinct variants collide, so we check the final names.
+fn check_unique_variant_names(enu: &DataEnum, names: &[String]) -> Result<(), syn::Error> {
+    let mut seen = HashMap::new();
+
+    enu.variants
+        .iter()
+        .zip(names)
+        .map(|(variant, name)| match seen.insert(name, variant) {
+            Some(first) => {
+                let mut err = syn::Error::new_spanned(
+                    &variant.ident,
+                    format!(
+                        "variants `{}` and `{}` are both named \"{}\" in the schema",
+                        first.ident, variant.ident, name
+                    ),
+                );
+                err.combine(syn::Error::new_spanned(
+                    &first.ident,
+                    format!("`{}` is named \"{}\" here", first.ident, name),
+                ));
+                Err(err)
+            }
+            None => Ok(()),
+        })
+        .collect_fallible()
+}
+
+/// Doc comments only end up in the schema with
+/// `GeneratorBuilder::descriptions`.
+fn with_description(schema: TokenStream, doc: &Option<String>) -> TokenStream {
+    match doc {
+        Some(doc) => quote! {
+            {
+                let schema = #schema;
+                if gen.descriptions() {
+                    schema.with_metadata("description", #doc)
+                } else {
+                    schema
+                }
+            }
+        },
+        None => schema,
+    }
+}
+
+fn with_deprecation(schema: TokenStream, deprecation: &Option<Deprecation>) -> TokenStream {
+    match deprecation {
+        Some(Deprecation { note: Some(note) }) => quote! {
+            #schema
+                .with_metadata("deprecated", true)
+                .with_metadata("deprecationNote", #note)
+        },
+        Some(Deprecation { note: None }) => quote! { #schema.with_metadata("deprecated", true) },
+        None => schema,
+    }
+}
+
+fn with_fuzz_hint(schema: TokenStream, hint: &Option<String>) -> TokenStream {
+    match hint {
+        Some(hint) => quote! { #schema.with_fuzz_hint(#hint) },
+        None => schema,
+    }
+}
+
+/// Like [`with_metadata`], but entries the schema already has are kept.
+fn with_metadata_preserving(schema: TokenStream, meta: &HashMap<String, String>) -> TokenStream {
+    if meta.is_empty() {
+        return schema;
+    }
+
+    let keys = meta.keys();
+    let values = meta.values();
+    quote! {
+        ::jtd_derive::__private::with_metadata_preserving(#schema, &[#((#keys, #values)),*])
+    }
+}
+
+/// Wrap the schema expression so that metadata entries get added to it.
+/// Emits nothing extra if there are none.
+fn with_metadata(schema: TokenStream, meta: &HashMap<String, String>) -> TokenStream {
+    if meta.is_empty() {
+        return schema;
+    }
+
     let keys = meta.keys();
     let values = meta.values();
-    quote! { [#((#keys, #values.parse::<::serde_json::Value>().unwrap())),*] }
+    quote! {
+        ::jtd_derive::__private::with_metadata(#schema, &[#((#keys, #values)),*])
+    }
 }
 
+/// Add the entries returned by a `#[typedef(metadata_with = "...")]`
+/// function, which is called every time the schema is generated.
+fn with_metadata_with(schema: TokenStream, path: &Option<Path>) -> TokenStream {
+    match path {
+        Some(path) => quote! {
+            {
+                let mut schema = #schema;
+                schema.metadata.extend(#path());
+                schema
+            }
+        },
+        None => schema,
+    }
+}
+
+/// `deny_unknown_fields` is the container's setting, unless a variant
+/// overrides it. Variants of internally tagged enums pass their ident and the
+/// tag, which none of their fields can be named.
 fn gen_named_fields(
     ctx: &Container,
-    fields: &FieldsNamed,
-    rename_rule: Option<RenameRule>,
+    fields_named: &FieldsNamed,
+    rename_all: bool,
+    deny_unknown_fields: bool,
+    variant_tag: Option<(&Ident, &str)>,
 ) -> Result<TokenStream, syn::Error> {
-    let fields: Vec<_> = fields
+    let fields: Vec<_> = fields_named
         .named
         .iter()
         .map(Field::from_syn_field)
         .collect_fallible()?;
 
-    let mut idents: Vec<_> = fields.iter().map(|f| f.ident.clone()).collect();
-    let types: Vec<_> = fields.iter().map(|f| f.ty.clone()).collect();
-    let metas: Vec<_> = fields.into_iter().map(|f| gen_metadata(&f.meta)).collect();
+    let schemas: Vec<_> = fields
+        .iter()
+        .map(|f| {
+            let ty = &f.ty;
+            let schema = match (&f.schema_with, &f.enum_values) {
+                (Some(path), _) => quote! { #path(gen) },
+                (None, Some(values)) => quote! {
+                    ::jtd_derive::__private::string_enum(gen.sub_schema::<#ty>(), &[#(#values),*])
+                },
+                (None, None) if f.inline => quote! { gen.sub_schema_inline::<#ty>() },
+                (None, None) if f.by_ref => quote! { gen.sub_schema_ref::<#ty>() },
+                (None, None) => quote! { gen.sub_schema::<#ty>() },
+            };
+            let schema = if f.nullable {
+                quote! { #schema.with_nullable(true) }
+            } else {
+                schema
+            };
+            let schema = with_description(schema, &f.doc);
+            let schema = match f.metadata_merge {
+                MetadataMerge::Override => with_metadata(schema, &f.meta),
+                MetadataMerge::Preserve => with_metadata_preserving(schema, &f.meta),
+            };
+            let schema = with_metadata_with(schema, &f.metadata_with);
+            let schema = with_fuzz_hint(schema, &f.fuzz_hint);
+            let schema = with_deprecation(schema, &f.deprecated);
+            let schema = constraints::apply(schema, &f.constraints);
+            if f.since.is_some() || f.until.is_some() {
+                let since = f.since.iter();
+                let until = f.until.iter();
+                quote! {
+                    #schema.with_metadata(
+                        "versions",
+                        ::jtd_derive::__private::serde_json::json!({
+                            #("since": #since,)*
+                            #("until": #until,)*
+                        }),
+                    )
+                }
+            } else {
+                schema
+            }
+        })
+        .collect();
 
-    if let Some(rule) = rename_rule {
-        for ident in idents.iter_mut() {
-            *ident = rule.apply_to_field(&ident.to_string());
-        }
+    let names = |direction| -> Vec<String> {
+        let rule = match (rename_all, direction) {
+            (false, _) => None,
+            (true, Direction::Serialize) => ctx.rename_rule_serialize,
+            (true, Direction::Deserialize) => ctx.rename_rule,
+        };
+        fields
+            .iter()
+            .map(|f| {
+                let rename = match direction {
+                    Direction::Serialize => &f.rename_serialize,
+                    Direction::Deserialize => &f.rename,
+                };
+                match (rename, rule) {
+                    (Some(rename), _) => rename.clone(),
+                    (None, Some(rule)) => rule.apply_to_field(&f.ident),
+                    (None, None) => f.ident.clone(),
+                }
+            })
+            .collect()
+    };
+    let (ser_names, de_names) = (names(Direction::Serialize), names(Direction::Deserialize));
+
+    // Fields only present in one direction can share a name with fields only
+    // present in the other, so check each direction separately. Flattened
+    // fields don't have a name of their own.
+    for (skipped, names) in [
+        ((|f| f.skip_serializing) as fn(&Field) -> bool, &ser_names),
+        (|f| f.skip_deserializing, &de_names),
+    ] {
+        let present = fields_named
+            .named
+            .iter()
+            .zip(&fields)
+            .zip(names)
+            .filter(|((_, f), _)| !skipped(f) && !f.flatten)
+            .map(|((field, _), name)| (field, name));
+        check_unique_field_names(present)?;
     }
+    if let Some((variant, tag)) = variant_tag {
+        check_tag_clash(variant, tag, fields_named, &fields, &de_names, &ser_names)?;
+    }
+    let idents = de_names
+        .iter()
+        .zip(&ser_names)
+        .map(|(de, ser)| by_mode(de, ser));
 
-    let expanded_fields = quote! {#((#idents, {
-        let mut schema = gen.sub_schema::<#types>();
-        schema.metadata.extend(#metas);
-        schema
-    })),*};
+    let deserializing = quote! {
+        (gen.mode() == ::jtd_derive::SchemaMode::Deserialize)
+    };
+    let add = fields
+        .iter()
+        .zip(idents)
+        .zip(schemas)
+        .map(|((f, ident), schema)| {
+            let ty = &f.ty;
+            let add = if f.flatten {
+                quote! { gen.flatten_into::<#ty>(&mut props); }
+            } else {
+                // Fields with a default can be missing from the input, and
+                // fields with `skip_serializing_if` from the output.
+                let optional_input = ctx.default || f.default;
+                let optional_output = f.skip_serializing_if;
+                match (optional_input, optional_output) {
+                    (false, false) => quote! { props.property(#ident, #schema); },
+                    (true, true) => quote! { props.optional_property(#ident, #schema); },
+                    (optional_input, _) => {
+                        let optional = if optional_input {
+                            quote! { #deserializing }
+                        } else {
+                            quote! { !#deserializing }
+                        };
+                        quote! {
+                            if #optional {
+                                props.optional_property(#ident, #schema);
+                            } else {
+                                props.property(#ident, #schema);
+                            }
+                        }
+                    }
+                }
+            };
 
-    let additional = !ctx.deny_unknown_fields;
+            let add = match (f.skip_serializing, f.skip_deserializing) {
+                (false, false) => add,
+                (true, false) => quote! { if #deserializing { #add } },
+                (false, true) => quote! { if !#deserializing { #add } },
+                // `#[typedef(skip)]` or `#[serde(skip)]`
+                (true, true) => quote! {},
+            };
 
-    let (prop, optional) = if ctx.default {
-        (quote! {[].into()}, quote! {[#expanded_fields].into()})
-    } else {
-        (quote! {[#expanded_fields].into()}, quote! {[].into()})
-    };
+            if f.since.is_some() || f.until.is_some() {
+                let option = |v: &Option<String>| match v {
+                    Some(v) => quote! { ::std::option::Option::Some(#v) },
+                    None => quote! { ::std::option::Option::None },
+                };
+                let (since, until) = (option(&f.since), option(&f.until));
+                quote! {
+                    if gen.includes_version(#since, #until) { #add }
+                }
+            } else {
+                add
+            }
+        });
+    let additional = (!deny_unknown_fields).then(|| quote! { .additional_properties(true) });
 
-    Ok(parse_quote! {
-        Schema {
-            ty: SchemaType::Properties {
-                properties: #prop,
-                optional_properties: #optional,
-                additional_properties: #additional,
-            },
-            ..::jtd_derive::schema::Schema::default()
+    Ok(quote! {
+        {
+            let mut props = ::jtd_derive::schema::Schema::properties();
+            #(#add)*
+            props #additional .build()
         }
     })
 }
 
+/// A unit variant of an internally tagged enum is just the tag.
+fn gen_unit_variant(deny_unknown_fields: bool) -> TokenStream {
+    let additional = (!deny_unknown_fields).then(|| quote! { .additional_properties(true) });
+
+    quote! {
+        ::jtd_derive::schema::Schema::properties() #additional .build()
+    }
+}
+
+/// The payload's properties become the variant's properties, the way serde
+/// serializes internally tagged newtype variants.
+fn gen_newtype_variant(ty: &Type, deny_unknown_fields: bool) -> TokenStream {
+    let additional = (!deny_unknown_fields).then(|| quote! { .additional_properties(true) });
+
+    quote! {
+        {
+            let mut props = ::jtd_derive::schema::Schema::properties();
+            gen.flatten_into::<#ty>(&mut props);
+            props #additional .build()
+        }
+    }
+}
+
 fn unwrap_fields_named(fields: &Fields) -> &FieldsNamed {
     if let Fields::Named(named) = fields {
         named
@@ -324,31 +1065,36 @@ fn unwrap_fields_named(fields: &Fields) -> &FieldsNamed {
 
 fn enum_kind(ident: &Ident, e: &DataEnum) -> Result<EnumKind, syn::Error> {
     let (mut named, mut unit) = (None, None);
+    // a `#[serde(other)]` unit variant can go with either kind
+    let mut other = None;
 
-    for variant in &e.variants {
-        match variant.fields {
-            Fields::Named(_) => {
-                named = Some(variant);
-                if unit.is_some() {
-                    break;
+    e.variants
+        .iter()
+        .map(|variant| {
+            match &variant.fields {
+                Fields::Unit if VariantCtx::from_input(variant)?.serde_other => {
+                    other = Some(variant);
                 }
-            }
-            Fields::Unit => {
-                unit = Some(variant);
-                if named.is_some() {
-                    break;
+                Fields::Unit => unit = unit.or(Some(variant)),
+                // newtype variants are treated like struct variants, assuming
+                // the payload is a struct
+                Fields::Named(_) => named = named.or(Some(variant)),
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                    named = named.or(Some(variant))
+                }
+                Fields::Unnamed(_) => {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "Typedef can't support tuple variants",
+                    ))
                 }
             }
-            Fields::Unnamed(_) => {
-                return Err(syn::Error::new_spanned(
-                    variant,
-                    "Typedef can't support tuple variants",
-                ))
-            }
-        }
-    }
+            Ok(())
+        })
+        .collect_fallible::<()>()?;
 
     match (named, unit) {
+        (None, None) if other.is_some() => Ok(EnumKind::UnitVariants),
         (None, None) => Err(syn::Error::new_spanned(
             ident,
             "jtd-derive does not support empty enums",
@@ -377,6 +1123,29 @@ fn enum_kind(ident: &Ident, e: &DataEnum) -> Result<EnumKind, syn::Error> {
     }
 }
 
+/// Collects errors from checks that don't depend on each other, so that they
+/// can all be reported at once.
+#[derive(Default)]
+struct Errors(Option<syn::Error>);
+
+impl Errors {
+    /// The value, or `None` if there was an error to keep for later.
+    fn take<T>(&mut self, res: Result<T, syn::Error>) -> Option<T> {
+        res.map_err(|e| self.push(e)).ok()
+    }
+
+    fn push(&mut self, e: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(e),
+            None => self.0 = Some(e),
+        }
+    }
+
+    fn finish(self) -> Result<(), syn::Error> {
+        self.0.map_or(Ok(()), Err)
+    }
+}
+
 enum EnumKind {
     // the enum only has unit variants
     UnitVariants,
@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use syn::visit::{self, Visit};
+use syn::{Data, DeriveInput, Ident, Type, TypePath};
+
+use super::context::Container;
+use super::field::Field;
+use crate::iter_ext::IterExt as _;
+
+/// The type parameters the schema depends on, and so the ones that need a
+/// `JsonTypedef` bound. Like serde, we look for them in the types of fields
+/// that aren't skipped (`PhantomData` fields are skipped too).
+pub fn schema_params(ctx: &Container, input: &DeriveInput) -> Result<HashSet<Ident>, syn::Error> {
+    let params: HashSet<_> = input
+        .generics
+        .type_params()
+        .map(|p| p.ident.clone())
+        .collect();
+    if params.is_empty() {
+        return Ok(params);
+    }
+
+    let mut finder = ParamFinder {
+        params: &params,
+        found: HashSet::new(),
+    };
+    for ty in schema_types(ctx, input)? {
+        finder.visit_type(&ty);
+    }
+
+    Ok(finder.found)
+}
+
+/// The types of everything the schema is generated from.
+fn schema_types(ctx: &Container, input: &DeriveInput) -> Result<Vec<Type>, syn::Error> {
+    if let Some(values) = &ctx.values {
+        return Ok(vec![values.clone()]);
+    }
+
+    let fields: Vec<_> = match &input.data {
+        Data::Struct(s) => s.fields.iter().collect(),
+        Data::Enum(e) => e.variants.iter().flat_map(|v| &v.fields).collect(),
+        Data::Union(_) => vec![],
+    };
+
+    let types: Vec<_> = fields
+        .into_iter()
+        .map(|f| {
+            if f.ident.is_none() {
+                // newtypes and newtype variants
+                return Ok(Some(f.ty.clone()));
+            }
+
+            let field = Field::from_syn_field(f)?;
+            let skipped = field.skip_serializing && field.skip_deserializing;
+            Ok((!skipped && field.schema_with.is_none()).then_some(field.ty))
+        })
+        .collect_fallible()?;
+
+    Ok(types.into_iter().flatten().collect())
+}
+
+struct ParamFinder<'a> {
+    params: &'a HashSet<Ident>,
+    found: HashSet<Ident>,
+}
+
+impl<'ast> Visit<'ast> for ParamFinder<'_> {
+    fn visit_type_path(&mut self, ty: &'ast TypePath) {
+        // `T`, but also `T::Assoc`
+        if ty.qself.is_none() {
+            if let Some(first) = ty.path.segments.first() {
+                if self.params.contains(&first.ident) {
+                    self.found.insert(first.ident.clone());
+                }
+            }
+        }
+
+        visit::visit_type_path(self, ty);
+    }
+}
@@ -1,8 +1,14 @@
+pub mod constraints;
 mod container;
 mod field;
+pub mod lint;
+#[cfg(feature = "serde_with")]
+mod serde_as;
+mod variant;
 
 pub use container::Container;
-pub use field::FieldCtx;
+pub use field::{FieldCtx, MetadataMerge};
+pub use variant::VariantCtx;
 
 use sdi::attr::RenameRule;
 use serde_derive_internals as sdi;
@@ -13,18 +19,19 @@ use crate::iter_ext::IterExt as _;
 const ATTR_IDENT: &str = "typedef";
 const SERDE_ATTR_IDENT: &str = "serde";
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// `#[deprecated]` or `#[typedef(deprecated)]`, with the note if there is one.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Deprecation {
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum TagType {
+    #[default]
     External,
     Internal(String),
 }
 
-impl Default for TagType {
-    fn default() -> Self {
-        Self::External
-    }
-}
-
 fn collect_attrs(
     attrs: &[Attribute],
     path: &str,
@@ -70,7 +77,148 @@ fn collect_attrs(
         .flatten())
 }
 
-fn parse_rename_rule(args: impl Iterator<Item = Meta>) -> Option<RenameRule> {
+/// Read a `deprecated`, `deprecated = "note"` or
+/// `deprecated(since = "...", note = "...")` meta, which is how both
+/// `#[deprecated]` and `#[typedef(deprecated)]` look.
+fn parse_deprecation(meta: &Meta) -> Result<Deprecation, syn::Error> {
+    match meta {
+        Meta::Path(_) => Ok(Deprecation::default()),
+        Meta::NameValue(MetaNameValue {
+            lit: Lit::Str(s), ..
+        }) => Ok(Deprecation {
+            note: Some(s.value()),
+        }),
+        Meta::List(l) => Ok(Deprecation {
+            note: l.nested.iter().find_map(|nested| match nested {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(s),
+                    ..
+                })) if path.is_ident("note") => Some(s.value()),
+                _ => None,
+            }),
+        }),
+        meta => Err(syn::Error::new_spanned(
+            meta,
+            "expected something like `deprecated = \"use something else\"`",
+        )),
+    }
+}
+
+/// The Rust `#[deprecated]` attribute among `attrs`, if there is one.
+fn find_deprecation(attrs: &[Attribute]) -> Result<Option<Deprecation>, syn::Error> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("deprecated"))
+        .map(|attr| parse_deprecation(&attr.parse_meta()?))
+        .next()
+        .transpose()
+}
+
+/// The JSON text of a `#[typedef(metadata(...))]` value. Literals become
+/// the matching JSON scalar - strings stay strings even if they look like
+/// JSON. Anything else goes through `metadata_json`.
+fn metadata_value(lit: Lit) -> Result<String, syn::Error> {
+    let value = match &lit {
+        Lit::Str(s) => serde_json::Value::String(s.value()),
+        Lit::Int(i) => serde_json::Value::from(i.base10_parse::<u64>()?),
+        Lit::Float(f) => serde_json::Number::from_f64(f.base10_parse()?)
+            .map(serde_json::Value::Number)
+            .ok_or_else(|| syn::Error::new_spanned(f, "expected a finite number"))?,
+        Lit::Bool(b) => serde_json::Value::Bool(b.value),
+        lit => {
+            return Err(syn::Error::new_spanned(
+                lit,
+                "expected a string, integer, float or bool literal",
+            ))
+        }
+    };
+    Ok(value.to_string())
+}
+
+/// The entries of a `#[typedef(metadata_json = "...")]` object, with values
+/// as JSON text like those from [`metadata_value`].
+fn metadata_json(meta: &Meta) -> Result<Vec<(String, String)>, syn::Error> {
+    let Meta::NameValue(MetaNameValue {
+        lit: Lit::Str(s), ..
+    }) = meta
+    else {
+        return Err(syn::Error::new_spanned(
+            meta,
+            "expected something like `metadata_json = r#\"{ \"key\": \"value\" }\"#`",
+        ));
+    };
+
+    match serde_json::from_str(&s.value()) {
+        Ok(serde_json::Value::Object(entries)) => Ok(entries
+            .into_iter()
+            .map(|(key, value)| (key, value.to_string()))
+            .collect()),
+        Ok(_) => Err(syn::Error::new_spanned(s, "expected a JSON object")),
+        Err(e) => Err(syn::Error::new_spanned(s, format!("invalid JSON: {}", e))),
+    }
+}
+
+/// Join the `///` doc comment lines in `attrs`, if there are any.
+fn parse_doc(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<_> = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(s), ..
+            })) => Some(s.value()),
+            _ => None,
+        })
+        .collect();
+
+    // `/// foo` becomes `#[doc = " foo"]`
+    let doc = lines
+        .iter()
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let doc = doc.trim();
+
+    (!doc.is_empty()).then(|| doc.to_string())
+}
+
+/// The names given by a serde `rename = "..."` or
+/// `rename(serialize = "...", deserialize = "...")` attribute, as
+/// `(serialize, deserialize)`.
+fn parse_serde_rename(meta: &Meta) -> (Option<String>, Option<String>) {
+    if !meta.path().is_ident("rename") {
+        return (None, None);
+    }
+
+    match meta {
+        Meta::NameValue(MetaNameValue {
+            lit: Lit::Str(s), ..
+        }) => (Some(s.value()), Some(s.value())),
+        Meta::List(l) => {
+            let mut names = (None, None);
+            for nested in &l.nested {
+                if let NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Str(s),
+                    ..
+                })) = nested
+                {
+                    if path.is_ident("serialize") {
+                        names.0 = Some(s.value());
+                    } else if path.is_ident("deserialize") {
+                        names.1 = Some(s.value());
+                    }
+                }
+            }
+            names
+        }
+        _ => (None, None),
+    }
+}
+
+/// The `rename_all` rule for one direction, `"serialize"` or `"deserialize"`.
+fn parse_rename_rule(args: impl Iterator<Item = Meta>, direction: &str) -> Option<RenameRule> {
     let rename_all_args = args.filter(|meta| {
         meta.path()
             .get_ident()
@@ -90,7 +238,7 @@ fn parse_rename_rule(args: impl Iterator<Item = Meta>) -> Option<RenameRule> {
                             if !name_value
                                 .path
                                 .get_ident()
-                                .map(|id| id.to_string().as_str() == "deserialize")
+                                .map(|id| *id == direction)
                                 .unwrap_or_default()
                             {
                                 return None;
@@ -0,0 +1,529 @@
+//! Validation constraints, declared with `#[typedef(...)]` or for other
+//! crates' derives, translated into schema metadata. Metadata keys follow the
+//! JSON Schema keyword names.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Expr, Lit, Meta, MetaNameValue};
+
+/// A metadata entry derived from a validation attribute. The value is an
+/// expression that gets turned into JSON at schema generation time, so that
+/// things like consts work too.
+pub struct Constraint {
+    pub key: &'static str,
+    pub value: TokenStream,
+}
+
+/// The metadata key for a constraint parameter, like `min_length`.
+pub fn key(param: &str) -> Option<&'static str> {
+    Some(match param {
+        "minimum" => "minimum",
+        "maximum" => "maximum",
+        "exclusive_minimum" => "exclusiveMinimum",
+        "exclusive_maximum" => "exclusiveMaximum",
+        "multiple_of" => "multipleOf",
+        "min_length" => "minLength",
+        "max_length" => "maxLength",
+        "pattern" => "pattern",
+        "min_items" => "minItems",
+        "max_items" => "maxItems",
+        "unique_items" => "uniqueItems",
+        "min_properties" => "minProperties",
+        "max_properties" => "maxProperties",
+        _ => return None,
+    })
+}
+
+/// Read a `#[typedef(...)]` constraint parameter. Numbers can be given as
+/// literals or, since attributes can't hold negative numbers or paths, as
+/// strings holding an expression, like `minimum = "-5"` or `maximum = "MAX"`.
+pub fn from_typedef(meta: &Meta) -> Result<Constraint, syn::Error> {
+    let key = meta
+        .path()
+        .get_ident()
+        .and_then(|ident| key(&ident.to_string()))
+        .expect("only called for constraint parameters");
+
+    let value = match meta {
+        Meta::Path(_) if key == "uniqueItems" => quote! { true },
+        Meta::NameValue(MetaNameValue { lit, .. }) => match lit {
+            Lit::Str(s) if key == "pattern" => quote! { #s },
+            Lit::Str(s) => {
+                let expr: Expr = s.parse()?;
+                quote! { #expr }
+            }
+            Lit::Int(_) | Lit::Float(_) | Lit::Bool(_) => quote! { #lit },
+            lit => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "expected a number or a string holding an expression",
+                ))
+            }
+        },
+        meta => {
+            return Err(syn::Error::new_spanned(
+                meta,
+                "expected something like `minimum = 1`",
+            ))
+        }
+    };
+
+    Ok(Constraint { key, value })
+}
+
+#[cfg(feature = "serde_valid")]
+pub use serde_valid::from_serde_valid;
+
+#[cfg(not(feature = "serde_valid"))]
+pub fn from_serde_valid(_attrs: &[syn::Attribute]) -> Result<Vec<Constraint>, syn::Error> {
+    Ok(vec![])
+}
+
+#[cfg(feature = "serde_valid")]
+mod serde_valid {
+    use proc_macro2::TokenStream;
+    use quote::quote;
+    use syn::parse::{Parse, ParseStream};
+    use syn::punctuated::Punctuated;
+    use syn::{Attribute, Expr, Ident, Token};
+
+    use super::Constraint;
+    use crate::iter_ext::IterExt as _;
+
+    const VALIDATE_ATTR_IDENT: &str = "validate";
+
+    /// Read `serde_valid`'s `#[validate(...)]` attributes. Anything that isn't
+    /// a known constraint (custom validators, messages, nested validation) is
+    /// left for `serde_valid` to deal with.
+    pub fn from_serde_valid(attrs: &[Attribute]) -> Result<Vec<Constraint>, syn::Error> {
+        let args = attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident(VALIDATE_ATTR_IDENT))
+            // a bare `#[validate]` means "validate the nested value"
+            .filter(|attr| !attr.tokens.is_empty())
+            .map(|attr| attr.parse_args_with(Punctuated::<Arg, Token![,]>::parse_terminated))
+            .collect_fallible::<Vec<_>>()?;
+
+        args.into_iter()
+            .flatten()
+            .filter_map(|arg| arg.into_constraint().transpose())
+            .collect_fallible()
+    }
+
+    enum ArgValue {
+        None,
+        Expr(Box<Expr>),
+        List(TokenStream),
+    }
+
+    struct Arg {
+        ident: Ident,
+        value: ArgValue,
+    }
+
+    impl Parse for Arg {
+        fn parse(input: ParseStream) -> syn::Result<Self> {
+            let ident = input.parse()?;
+            let value = if input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                ArgValue::Expr(Box::new(input.parse()?))
+            } else if input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in input);
+                ArgValue::List(content.parse()?)
+            } else {
+                ArgValue::None
+            };
+
+            Ok(Self { ident, value })
+        }
+    }
+
+    impl Arg {
+        fn into_constraint(self) -> Result<Option<Constraint>, syn::Error> {
+            let key = match self.ident.to_string().as_str() {
+                "enumerate" => "enum",
+                param => match super::key(param) {
+                    Some(key) => key,
+                    None => return Ok(None),
+                },
+            };
+
+            let value = match self.value {
+                ArgValue::None if key == "uniqueItems" => quote! { true },
+                ArgValue::Expr(expr) => quote! { #expr },
+                // older `serde_valid` versions take `enumerate(1, 2, 3)`
+                ArgValue::List(list) if key == "enum" => {
+                    let items = syn::parse::Parser::parse2(
+                        Punctuated::<Expr, Token![,]>::parse_terminated,
+                        list,
+                    )?
+                    .into_iter();
+                    quote! { [#(#items),*] }
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        self.ident,
+                        "jtd-derive doesn't understand this form of the constraint",
+                    ))
+                }
+            };
+
+            Ok(Some(Constraint { key, value }))
+        }
+    }
+}
+
+#[cfg(feature = "validator")]
+pub use validator::from_validator;
+
+#[cfg(not(feature = "validator"))]
+pub fn from_validator(
+    _attrs: &[syn::Attribute],
+    _ty: &syn::Type,
+) -> Result<Vec<Constraint>, syn::Error> {
+    Ok(vec![])
+}
+
+#[cfg(feature = "validator")]
+mod validator {
+    use quote::quote;
+    use syn::punctuated::Punctuated;
+    use syn::{Attribute, Token, Type};
+
+    use super::{parse_nested, Arg, Constraint};
+    use crate::iter_ext::IterExt as _;
+
+    const VALIDATE_ATTR_IDENT: &str = "validate";
+
+    /// Read `validator`'s `#[validate(...)]` attributes. Validators without a
+    /// metadata equivalent (custom functions, `contains`, `must_match`...)
+    /// are left alone.
+    ///
+    /// `length` means different things for strings, collections and maps, so
+    /// the field's type is needed to pick the right metadata keys. This can
+    /// only be guessed from the type's name.
+    pub fn from_validator(attrs: &[Attribute], ty: &Type) -> Result<Vec<Constraint>, syn::Error> {
+        let args = attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident(VALIDATE_ATTR_IDENT))
+            .filter(|attr| !attr.tokens.is_empty())
+            .map(|attr| attr.parse_args_with(Punctuated::<Arg, Token![,]>::parse_terminated))
+            .collect_fallible::<Vec<_>>()?;
+
+        let mut constraints = vec![];
+        for arg in args.into_iter().flatten() {
+            into_constraints(arg, ty, &mut constraints)?;
+        }
+        Ok(constraints)
+    }
+
+    fn into_constraints(arg: Arg, ty: &Type, out: &mut Vec<Constraint>) -> syn::Result<()> {
+        let format = |out: &mut Vec<Constraint>, format: &str| {
+            out.push(Constraint {
+                key: "format",
+                value: quote! { #format },
+            })
+        };
+
+        match (arg.ident.to_string().as_str(), arg.nested) {
+            ("email", None) => format(out, "email"),
+            ("url", None) => format(out, "uri"),
+            ("length", Some(nested)) => {
+                let (min, max) = super::length_keys(ty);
+                for arg in parse_nested(nested)? {
+                    let Some(value) = arg.value else { continue };
+                    let value = super::value(value)?;
+                    match arg.ident.to_string().as_str() {
+                        "min" => out.push(Constraint { key: min, value }),
+                        "max" => out.push(Constraint { key: max, value }),
+                        "equal" => {
+                            out.push(Constraint {
+                                key: min,
+                                value: value.clone(),
+                            });
+                            out.push(Constraint { key: max, value });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            ("range", Some(nested)) => {
+                for arg in parse_nested(nested)? {
+                    let key = match arg.ident.to_string().as_str() {
+                        "min" => "minimum",
+                        "max" => "maximum",
+                        "exclusive_min" => "exclusiveMinimum",
+                        "exclusive_max" => "exclusiveMaximum",
+                        _ => continue,
+                    };
+                    let Some(value) = arg.value else { continue };
+                    out.push(Constraint {
+                        key,
+                        value: super::value(value)?,
+                    });
+                }
+            }
+            // the regex is only known at runtime
+            ("regex", Some(nested)) => {
+                for arg in parse_nested(nested)? {
+                    if let ("path", Some(value)) = (arg.ident.to_string().as_str(), arg.value) {
+                        let regex = super::value(value)?;
+                        out.push(Constraint {
+                            key: "pattern",
+                            value: quote! { (#regex).as_str() },
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "garde")]
+pub use garde::from_garde;
+
+#[cfg(not(feature = "garde"))]
+pub fn from_garde(
+    _attrs: &[syn::Attribute],
+    _ty: &syn::Type,
+) -> Result<Vec<Constraint>, syn::Error> {
+    Ok(vec![])
+}
+
+#[cfg(feature = "garde")]
+mod garde {
+    use quote::quote;
+    use syn::punctuated::Punctuated;
+    use syn::{Attribute, Expr, Lit, Token, Type};
+
+    use super::{parse_nested, Arg, Constraint};
+    use crate::iter_ext::IterExt as _;
+
+    const GARDE_ATTR_IDENT: &str = "garde";
+
+    /// Read `garde`'s `#[garde(...)]` attributes. Rules without a metadata
+    /// equivalent (`custom`, `contains`, `dive`...) are left alone, and so
+    /// are the rules nested in `inner(...)`, which apply to the items of a
+    /// collection.
+    ///
+    /// `length` modes (`bytes`, `chars`...) are ignored - the metadata keys
+    /// are picked based on whether the field looks like a string, a sequence
+    /// or a map.
+    pub fn from_garde(attrs: &[Attribute], ty: &Type) -> Result<Vec<Constraint>, syn::Error> {
+        let args = attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident(GARDE_ATTR_IDENT))
+            .map(|attr| attr.parse_args_with(Punctuated::<Arg, Token![,]>::parse_terminated))
+            .collect_fallible::<Vec<_>>()?;
+
+        let mut constraints = vec![];
+        for arg in args.into_iter().flatten() {
+            into_constraints(arg, ty, &mut constraints)?;
+        }
+        Ok(constraints)
+    }
+
+    fn into_constraints(arg: Arg, ty: &Type, out: &mut Vec<Constraint>) -> syn::Result<()> {
+        let mut format = |format: &str| {
+            out.push(Constraint {
+                key: "format",
+                value: quote! { #format },
+            })
+        };
+
+        match (arg.ident.to_string().as_str(), arg.nested) {
+            ("email", None) => format("email"),
+            ("url", None) => format("uri"),
+            ("ipv4", None) => format("ipv4"),
+            ("ipv6", None) => format("ipv6"),
+            ("length", Some(nested)) => {
+                let (min, max) = super::length_keys(ty);
+                for arg in parse_nested(nested)? {
+                    let Some(value) = arg.value else { continue };
+                    let value = super::value(value)?;
+                    match arg.ident.to_string().as_str() {
+                        "min" => out.push(Constraint { key: min, value }),
+                        "max" => out.push(Constraint { key: max, value }),
+                        "equal" => {
+                            out.push(Constraint {
+                                key: min,
+                                value: value.clone(),
+                            });
+                            out.push(Constraint { key: max, value });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            ("range", Some(nested)) => {
+                for arg in parse_nested(nested)? {
+                    let key = match arg.ident.to_string().as_str() {
+                        "min" => "minimum",
+                        "max" => "maximum",
+                        _ => continue,
+                    };
+                    let Some(value) = arg.value else { continue };
+                    out.push(Constraint {
+                        key,
+                        value: super::value(value)?,
+                    });
+                }
+            }
+            // either a literal or a regex only known at runtime
+            ("pattern", Some(nested)) => {
+                let value = match syn::parse2::<Expr>(nested)? {
+                    Expr::Lit(syn::ExprLit {
+                        lit: Lit::Str(s), ..
+                    }) => quote! { #s },
+                    regex => quote! { (#regex).as_str() },
+                };
+                out.push(Constraint {
+                    key: "pattern",
+                    value,
+                });
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// A validation rule like `email` or `length(min = 1)`, as `validator` and
+/// `garde` write them.
+#[cfg(any(feature = "validator", feature = "garde"))]
+struct Arg {
+    ident: syn::Ident,
+    nested: Option<TokenStream>,
+}
+
+#[cfg(any(feature = "validator", feature = "garde"))]
+impl syn::parse::Parse for Arg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        let nested = if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            Some(content.parse()?)
+        } else {
+            if input.peek(syn::Token![=]) {
+                // other crates' syntax, e.g. `serde_valid`'s `minimum = 1`
+                input.parse::<syn::Token![=]>()?;
+                input.parse::<syn::Expr>()?;
+            }
+            None
+        };
+
+        Ok(Self { ident, nested })
+    }
+}
+
+/// `min = 1` or a flag like `chars` inside something like `length(...)`.
+#[cfg(any(feature = "validator", feature = "garde"))]
+struct NestedArg {
+    ident: syn::Ident,
+    value: Option<syn::Expr>,
+}
+
+#[cfg(any(feature = "validator", feature = "garde"))]
+impl syn::parse::Parse for NestedArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        let value = if input.peek(syn::Token![=]) {
+            input.parse::<syn::Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self { ident, value })
+    }
+}
+
+#[cfg(any(feature = "validator", feature = "garde"))]
+fn parse_nested(
+    nested: TokenStream,
+) -> syn::Result<syn::punctuated::Punctuated<NestedArg, syn::Token![,]>> {
+    syn::parse::Parser::parse2(syn::punctuated::Punctuated::parse_terminated, nested)
+}
+
+/// Older `validator` versions take paths in strings, like `min = "MIN"`.
+#[cfg(any(feature = "validator", feature = "garde"))]
+fn value(expr: syn::Expr) -> syn::Result<TokenStream> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) => {
+            let expr: syn::Expr = s.parse()?;
+            Ok(quote! { #expr })
+        }
+        expr => Ok(quote! { #expr }),
+    }
+}
+
+#[cfg(any(feature = "validator", feature = "garde"))]
+enum CollectionKind {
+    String,
+    Sequence,
+    Map,
+}
+
+/// Whether a `length` constraint on `ty` is about characters, items or
+/// entries. This can only be guessed from the type's name.
+#[cfg(any(feature = "validator", feature = "garde"))]
+fn collection_kind(ty: &syn::Type) -> CollectionKind {
+    match ty {
+        syn::Type::Array(_) | syn::Type::Slice(_) => CollectionKind::Sequence,
+        syn::Type::Reference(r) => collection_kind(&r.elem),
+        syn::Type::Path(p) => {
+            let Some(last) = p.path.segments.last() else {
+                return CollectionKind::String;
+            };
+            match last.ident.to_string().as_str() {
+                "Vec" | "VecDeque" | "LinkedList" | "HashSet" | "BTreeSet" | "IndexSet" => {
+                    CollectionKind::Sequence
+                }
+                "HashMap" | "BTreeMap" | "IndexMap" => CollectionKind::Map,
+                // look through wrappers like `Option<Vec<T>>`
+                "Option" | "Box" => match &last.arguments {
+                    syn::PathArguments::AngleBracketed(args) => args
+                        .args
+                        .iter()
+                        .find_map(|arg| match arg {
+                            syn::GenericArgument::Type(ty) => Some(collection_kind(ty)),
+                            _ => None,
+                        })
+                        .unwrap_or(CollectionKind::String),
+                    _ => CollectionKind::String,
+                },
+                _ => CollectionKind::String,
+            }
+        }
+        _ => CollectionKind::String,
+    }
+}
+
+/// The metadata keys for the minimum and maximum length of a `ty`.
+#[cfg(any(feature = "validator", feature = "garde"))]
+fn length_keys(ty: &syn::Type) -> (&'static str, &'static str) {
+    match collection_kind(ty) {
+        CollectionKind::String => ("minLength", "maxLength"),
+        CollectionKind::Sequence => ("minItems", "maxItems"),
+        CollectionKind::Map => ("minProperties", "maxProperties"),
+    }
+}
+
+/// Wrap the schema expression so that the constraints get added to its
+/// metadata.
+pub fn apply(schema: TokenStream, constraints: &[Constraint]) -> TokenStream {
+    let keys = constraints.iter().map(|c| c.key);
+    let values = constraints.iter().map(|c| &c.value);
+
+    quote! {
+        #schema #(.with_metadata(#keys, ::jtd_derive::__private::serde_json::json!(#values)))*
+    }
+}
@@ -2,14 +2,13 @@ use std::collections::HashMap;
 
 use sdi::attr::RenameRule;
 use serde_derive_internals as sdi;
-use syn::{DeriveInput, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Type};
+use syn::{DeriveInput, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path, Type};
 
-use super::{collect_attrs, TagType, ATTR_IDENT, SERDE_ATTR_IDENT};
+use super::{collect_attrs, Deprecation, TagType, ATTR_IDENT, SERDE_ATTR_IDENT};
 use crate::iter_ext::IterExt as _;
 
 #[derive(Default)]
 pub struct Container {
-    pub no_serde: bool,
     pub tag_type: TagType,
     pub deny_unknown_fields: bool,
     pub transparent: bool,
@@ -17,7 +16,27 @@ pub struct Container {
     pub type_try_from: Option<Type>,
     pub default: bool,
     pub rename_rule: Option<RenameRule>,
+    /// Like `rename_rule`, but for serialization.
+    pub rename_rule_serialize: Option<RenameRule>,
     pub metadata: HashMap<String, String>,
+    pub fuzz_hint: Option<String>,
+    /// Whether serde attributes we don't understand are errors.
+    pub strict: bool,
+    pub discriminants: bool,
+    pub key: bool,
+    pub variant_tags: bool,
+    /// The type's doc comment.
+    pub doc: Option<String>,
+    /// The Typedef integer type a unit enum is represented as, e.g. `"uint8"`.
+    pub repr: Option<String>,
+    pub deprecated: Option<Deprecation>,
+    /// The value type of a struct (de)serialized as a map, e.g. by a custom
+    /// serde impl.
+    pub values: Option<Type>,
+    /// A function returning metadata entries at generation time.
+    pub metadata_with: Option<Path>,
+    /// The name the type goes by in the schema, if it's been renamed.
+    pub rename: Option<String>,
 }
 
 impl Container {
@@ -38,12 +57,26 @@ impl Container {
             sdi::attr::TagType::None =>
                 return Err(syn::Error::new_spanned(&input.ident, "this type uses the untagged enum representation, but `jtd_derive` doesn't support it")),
         };
+        // serde doesn't tell us if the container was renamed, but we can tell
+        // from the name. Like elsewhere, the deserialization name wins. It's
+        // only used with `#[typedef(serde_name)]` so that existing definition
+        // names don't change under anyone's feet.
+        let serde_name = serde.name().deserialize_name();
+        let serde_rename = (input.ident != serde_name).then_some(serde_name);
+        let mut use_serde_name = false;
         cont.deny_unknown_fields = serde.deny_unknown_fields();
         cont.transparent = serde.transparent();
         cont.type_from = serde.type_from().cloned();
         cont.type_try_from = serde.type_try_from().cloned();
         cont.default = !matches!(serde.default(), sdi::attr::Default::None);
-        cont.rename_rule = super::parse_rename_rule(collect_attrs(&input.attrs, SERDE_ATTR_IDENT)?);
+        cont.rename_rule = super::parse_rename_rule(
+            collect_attrs(&input.attrs, SERDE_ATTR_IDENT)?,
+            "deserialize",
+        );
+        cont.rename_rule_serialize =
+            super::parse_rename_rule(collect_attrs(&input.attrs, SERDE_ATTR_IDENT)?, "serialize");
+        cont.doc = super::parse_doc(&input.attrs);
+        cont.deprecated = super::find_deprecation(&input.attrs)?;
 
         let params = collect_attrs(&input.attrs, ATTR_IDENT)?;
         params
@@ -83,6 +116,20 @@ impl Container {
                             ))
                         }
                     }
+                    "additional_properties" => {
+                        if let Meta::NameValue(MetaNameValue {
+                            lit: Lit::Bool(b), ..
+                        }) = &p
+                        {
+                            cont.deny_unknown_fields = !b.value;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected `additional_properties = true` or `additional_properties = false`",
+                            ))
+                        }
+                    }
                     "transparent" => {
                         if let Meta::Path(_) = p {
                             cont.transparent = true;
@@ -130,6 +177,7 @@ impl Container {
                                 let rule = RenameRule::from_str(&s.value())
                                     .map_err(|e| syn::Error::new_spanned(v.lit, e))?;
                                 cont.rename_rule = Some(rule);
+                                cont.rename_rule_serialize = Some(rule);
                                 Ok(())
                             } else {
                                 Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
@@ -152,6 +200,150 @@ impl Container {
                             ))
                         }
                     }
+                    "strict" => {
+                        if let Meta::Path(_) = p {
+                            cont.strict = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `strict` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "values" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                cont.values = Some(s.parse()?);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `values = \"ValueType\"`",
+                            ))
+                        }
+                    }
+                    "deprecated" => {
+                        cont.deprecated = Some(super::parse_deprecation(&p)?);
+                        Ok(())
+                    }
+                    "repr" => {
+                        if let Meta::NameValue(MetaNameValue {
+                            lit: Lit::Str(s), ..
+                        }) = &p
+                        {
+                            let repr = s.value();
+                            if !["int8", "uint8", "int16", "uint16", "int32", "uint32"]
+                                .contains(&repr.as_str())
+                            {
+                                return Err(syn::Error::new_spanned(
+                                    s,
+                                    "expected one of \"int8\", \"uint8\", \"int16\", \"uint16\", \"int32\" or \"uint32\"",
+                                ));
+                            }
+                            cont.repr = Some(repr);
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `repr = \"uint8\"`",
+                            ))
+                        }
+                    }
+                    "discriminants" => {
+                        if let Meta::Path(_) = p {
+                            cont.discriminants = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `discriminants` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "key" => {
+                        if let Meta::Path(_) = p {
+                            cont.key = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `key` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "variant_tags" => {
+                        if let Meta::Path(_) = p {
+                            cont.variant_tags = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `variant_tags` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "fuzz_hint" => {
+                        if let Meta::NameValue(MetaNameValue {
+                            lit: Lit::Str(s), ..
+                        }) = &p
+                        {
+                            cont.fuzz_hint = Some(s.value());
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `fuzz_hint = \"en_us.internet.email\"`",
+                            ))
+                        }
+                    }
+                    "serde_name" => {
+                        if let Meta::Path(_) = p {
+                            use_serde_name = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `serde_name` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "rename" => {
+                        if let Meta::NameValue(MetaNameValue {
+                            lit: Lit::Str(s), ..
+                        }) = &p
+                        {
+                            cont.rename = Some(s.value());
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `rename = \"...\"`",
+                            ))
+                        }
+                    }
+                    "metadata_with" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                cont.metadata_with = Some(s.parse()?);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `metadata_with = \"path::to::fn\"`",
+                            ))
+                        }
+                    }
+                    "metadata_json" => {
+                        cont.metadata.extend(super::metadata_json(&p)?);
+                        Ok(())
+                    }
                     "metadata" => {
                         if let Meta::List(MetaList { nested, .. }) = p {
                             let metadata = nested
@@ -169,14 +361,7 @@ impl Container {
                                                 "expected an ident, not a multi-segment path",
                                             ),
                                         )?;
-                                        if let Lit::Str(val) = lit {
-                                            Ok((key, val.value()))
-                                        } else {
-                                            Err(syn::Error::new_spanned(
-                                                lit,
-                                                "expected string literal",
-                                            ))
-                                        }
+                                        Ok((key, super::metadata_value(lit)?))
                                     } else {
                                         Err(syn::Error::new_spanned(
                                             nested_meta,
@@ -184,9 +369,9 @@ impl Container {
                                         ))
                                     }
                                 })
-                                .collect_fallible()?;
+                                .collect_fallible::<Vec<_>>()?;
 
-                            cont.metadata = metadata;
+                            cont.metadata.extend(metadata);
                             Ok(())
                         } else {
                             Err(syn::Error::new_spanned(
@@ -201,7 +386,11 @@ impl Container {
                     )),
                 }
             })
-            .collect_fallible()?;
+            .collect_fallible::<()>()?;
+
+        if use_serde_name && cont.rename.is_none() {
+            cont.rename = serde_rename;
+        }
 
         Ok(cont)
     }
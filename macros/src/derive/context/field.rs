@@ -1,18 +1,76 @@
 use std::collections::HashMap;
 
-use syn::{Field, Lit, Meta, MetaList, MetaNameValue, NestedMeta};
+use syn::{Field, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Path, Type};
 
-use super::{collect_attrs, ATTR_IDENT};
+use super::constraints::{self, Constraint};
+use super::{collect_attrs, Deprecation, ATTR_IDENT, SERDE_ATTR_IDENT};
 use crate::iter_ext::IterExt as _;
 
 #[derive(Default)]
 pub struct FieldCtx {
     pub metadata: HashMap<String, String>,
+    pub metadata_merge: MetadataMerge,
+    /// The property name, overriding the field name and any `rename_all`.
+    pub rename: Option<String>,
+    /// Like `rename`, but for serialization.
+    pub rename_serialize: Option<String>,
+    /// A `jtd-fuzz` hint.
+    pub fuzz_hint: Option<String>,
+    pub constraints: Vec<Constraint>,
+    /// The first API version the field appears in.
+    pub since: Option<String>,
+    /// The first API version the field no longer appears in.
+    pub until: Option<String>,
+    /// `#[serde(skip_serializing)]`
+    pub skip_serializing: bool,
+    /// `#[serde(skip_deserializing)]`
+    pub skip_deserializing: bool,
+    /// `#[serde(skip_serializing_if = "...")]`
+    pub skip_serializing_if: bool,
+    /// `#[serde(default)]` or `#[serde(default = "...")]`
+    pub default: bool,
+    /// `#[typedef(flatten)]` or `#[serde(flatten)]`
+    pub flatten: bool,
+    /// A `fn(&mut Generator) -> Schema` to use instead of the field type's
+    /// schema.
+    pub schema_with: Option<Path>,
+    /// A type to use the schema of instead of the field's declared type.
+    pub schema_as: Option<Type>,
+    /// The field's doc comment.
+    pub doc: Option<String>,
+    /// Whether the field can be `null` regardless of its type.
+    pub nullable: bool,
+    pub deprecated: Option<Deprecation>,
+    /// A function returning metadata entries at generation time.
+    pub metadata_with: Option<Path>,
+    /// Inline the field type's schema even if the generator would use a ref.
+    pub inline: bool,
+    /// Provide the field type's schema by ref even if the generator would
+    /// inline it.
+    pub by_ref: bool,
+    /// The only strings a stringly-typed field takes, making its schema an
+    /// enum.
+    pub enum_values: Option<Vec<String>>,
+}
+
+/// How field metadata is combined with the metadata the field's type already
+/// has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MetadataMerge {
+    /// Field entries replace the type's entries with the same key.
+    #[default]
+    Override,
+    /// The type's entries are kept, field entries only fill in missing keys.
+    Preserve,
 }
 
 impl FieldCtx {
     pub fn from_input(input: &Field) -> Result<Self, syn::Error> {
-        let mut field = Self::default();
+        let mut field = Self {
+            doc: super::parse_doc(&input.attrs),
+            deprecated: super::find_deprecation(&input.attrs)?,
+            ..Self::default()
+        };
 
         let params = collect_attrs(&input.attrs, ATTR_IDENT)?;
         params
@@ -26,6 +84,148 @@ impl FieldCtx {
                     .to_string()
                     .as_str()
                 {
+                    "rename" => {
+                        if let Meta::NameValue(MetaNameValue {
+                            lit: Lit::Str(s), ..
+                        }) = &p
+                        {
+                            field.rename = Some(s.value());
+                            field.rename_serialize = Some(s.value());
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `rename = \"...\"`",
+                            ))
+                        }
+                    }
+                    "skip" => {
+                        if let Meta::Path(_) = p {
+                            field.skip_serializing = true;
+                            field.skip_deserializing = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `skip` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "metadata_with" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                field.metadata_with = Some(s.parse()?);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `metadata_with = \"path::to::fn\"`",
+                            ))
+                        }
+                    }
+                    "schema_with" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                field.schema_with = Some(s.parse()?);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `schema_with = \"path::to::fn\"`",
+                            ))
+                        }
+                    }
+                    "as" => {
+                        if let Meta::NameValue(v) = p {
+                            if let Lit::Str(s) = v.lit {
+                                field.schema_as = Some(s.parse()?);
+                                Ok(())
+                            } else {
+                                Err(syn::Error::new_spanned(v.lit, "expected a string literal"))
+                            }
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `as = \"OtherType\"`",
+                            ))
+                        }
+                    }
+                    "enum_values" => {
+                        field.enum_values = Some(parse_enum_values(&p)?);
+                        Ok(())
+                    }
+                    "deprecated" => {
+                        field.deprecated = Some(super::parse_deprecation(&p)?);
+                        Ok(())
+                    }
+                    "nullable" => {
+                        if let Meta::Path(_) = p {
+                            field.nullable = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `nullable` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "inline" => {
+                        if let Meta::Path(_) = p {
+                            field.inline = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `inline` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "ref" => {
+                        if let Meta::Path(_) = p {
+                            field.by_ref = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `ref` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "flatten" => {
+                        if let Meta::Path(_) = p {
+                            field.flatten = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `flatten` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "fuzz_hint" => {
+                        if let Meta::NameValue(MetaNameValue {
+                            lit: Lit::Str(s), ..
+                        }) = &p
+                        {
+                            field.fuzz_hint = Some(s.value());
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `fuzz_hint = \"en_us.internet.email\"`",
+                            ))
+                        }
+                    }
+                    "metadata_json" => {
+                        field.metadata.extend(super::metadata_json(&p)?);
+                        Ok(())
+                    }
                     "metadata" => {
                         if let Meta::List(MetaList { nested, .. }) = p {
                             let metadata = nested
@@ -43,14 +243,7 @@ impl FieldCtx {
                                                 "expected an ident, not a multi-segment path",
                                             ),
                                         )?;
-                                        if let Lit::Str(val) = lit {
-                                            Ok((key, val.value()))
-                                        } else {
-                                            Err(syn::Error::new_spanned(
-                                                lit,
-                                                "expected string literal",
-                                            ))
-                                        }
+                                        Ok((key, super::metadata_value(lit)?))
                                     } else {
                                         Err(syn::Error::new_spanned(
                                             nested_meta,
@@ -58,9 +251,9 @@ impl FieldCtx {
                                         ))
                                     }
                                 })
-                                .collect_fallible()?;
+                                .collect_fallible::<Vec<_>>()?;
 
-                            field.metadata = metadata;
+                            field.metadata.extend(metadata);
                             Ok(())
                         } else {
                             Err(syn::Error::new_spanned(
@@ -69,14 +262,236 @@ impl FieldCtx {
                             ))
                         }
                     }
+                    "metadata_merge" => {
+                        if let Meta::NameValue(MetaNameValue {
+                            lit: Lit::Str(s), ..
+                        }) = &p
+                        {
+                            field.metadata_merge = match s.value().as_str() {
+                                "override" => MetadataMerge::Override,
+                                "preserve" => MetadataMerge::Preserve,
+                                _ => {
+                                    return Err(syn::Error::new_spanned(
+                                        s,
+                                        "expected \"override\" or \"preserve\"",
+                                    ))
+                                }
+                            };
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `metadata_merge = \"preserve\"`",
+                            ))
+                        }
+                    }
+                    param @ ("since" | "until") => {
+                        if let Meta::NameValue(MetaNameValue {
+                            lit: Lit::Str(s), ..
+                        }) = &p
+                        {
+                            let version = s.value();
+                            if !is_valid_version(&version) {
+                                return Err(syn::Error::new_spanned(
+                                    s,
+                                    "expected a version made of dot-separated numbers, like \"1.2\"",
+                                ));
+                            }
+                            if param == "since" {
+                                field.since = Some(version);
+                            } else {
+                                field.until = Some(version);
+                            }
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                format!("expected something like `{} = \"1.2\"`", param),
+                            ))
+                        }
+                    }
+                    param if constraints::key(param).is_some() => {
+                        field.constraints.push(constraints::from_typedef(&p)?);
+                        Ok(())
+                    }
                     _ => Err(syn::Error::new_spanned(
                         p.path(),
                         "unknown jtd-derive parameter",
                     )),
                 }
             })
-            .collect_fallible()?;
+            .collect_fallible::<()>()?;
+
+        if let (Some(_), Some(path)) = (&field.schema_as, &field.schema_with) {
+            return Err(syn::Error::new_spanned(
+                path,
+                "`as` and `schema_with` can't be used together",
+            ));
+        }
+        if let (true, Some(path)) = (field.inline || field.by_ref, &field.schema_with) {
+            return Err(syn::Error::new_spanned(
+                path,
+                "`inline` and `ref` can't be used together with `schema_with`",
+            ));
+        }
+        if field.by_ref && (field.inline || field.flatten) {
+            return Err(syn::Error::new_spanned(
+                input,
+                "`ref` can't be used together with `inline` or `flatten`",
+            ));
+        }
+        if field.enum_values.is_some() && (field.schema_as.is_some() || field.schema_with.is_some())
+        {
+            return Err(syn::Error::new_spanned(
+                input,
+                "`enum_values` can't be used together with `as` or `schema_with`",
+            ));
+        }
+
+        field
+            .constraints
+            .extend(constraints::from_serde_valid(&input.attrs)?);
+        field
+            .constraints
+            .extend(constraints::from_validator(&input.attrs, &input.ty)?);
+        field
+            .constraints
+            .extend(constraints::from_garde(&input.attrs, &input.ty)?);
+
+        let mut serde_with = None;
+        for meta in collect_attrs(&input.attrs, SERDE_ATTR_IDENT)? {
+            // `#[typedef(rename = "...")]` takes precedence
+            let (ser, de) = super::parse_serde_rename(&meta);
+            if let (Some(name), None) = (ser, &field.rename_serialize) {
+                field.rename_serialize = Some(name);
+            }
+            if let (Some(name), None) = (de, &field.rename) {
+                field.rename = Some(name);
+            }
+
+            let Some(ident) = meta.path().get_ident() else {
+                continue;
+            };
+            match ident.to_string().as_str() {
+                "skip" => {
+                    field.skip_serializing = true;
+                    field.skip_deserializing = true;
+                }
+                "skip_serializing" => field.skip_serializing = true,
+                "skip_deserializing" => field.skip_deserializing = true,
+                "skip_serializing_if" => field.skip_serializing_if = true,
+                "default" => field.default = true,
+                "flatten" => field.flatten = true,
+                "with" | "serialize_with" | "deserialize_with" => {
+                    let with = match &meta {
+                        Meta::NameValue(MetaNameValue {
+                            lit: Lit::Str(s), ..
+                        }) => Some(s.clone()),
+                        _ => None,
+                    };
+                    serde_with.get_or_insert((meta.path().clone(), with));
+                }
+                _ => {}
+            }
+        }
+
+        // `humantime_serde` has a wrapper type for what its functions do
+        if let (Some((_, Some(with))), None, None) =
+            (&serde_with, &field.schema_as, &field.schema_with)
+        {
+            let path: Path = with.parse()?;
+            if path.is_ident("humantime_serde") {
+                let ty = &input.ty;
+                field.schema_as = Some(syn::parse_quote! { #path::Serde<#ty> });
+                serde_with = None;
+            }
+        }
+
+        // `serde_with` adapters we know are as good as `#[typedef(as = "...")]`
+        #[cfg(feature = "serde_with")]
+        if let (Some((_, Some(with))), None, None) =
+            (&serde_with, &field.schema_as, &field.schema_with)
+        {
+            if let Some((ty, constraints)) = super::serde_as::adapter_schema(with, &input.ty)? {
+                field.schema_as = Some(ty);
+                field.constraints.extend(constraints);
+                serde_with = None;
+            }
+        }
+
+        // A custom (de)serializer likely doesn't produce what the field type's
+        // schema describes, so we want to be told what it does produce.
+        if let (Some((path, _)), None, None) = (serde_with, &field.schema_as, &field.schema_with) {
+            return Err(syn::Error::new_spanned(
+                &path,
+                format!(
+                    "the schema of a field with `#[serde({})]` may be incorrect; \
+                     specify it with `#[typedef(as = \"...\")]` or \
+                     `#[typedef(schema_with = \"...\")]`",
+                    path.get_ident().unwrap()
+                ),
+            ));
+        }
+
+        // a flattened field contributes its type's properties, not a schema of
+        // its own, so there's nothing for these to apply to
+        if field.flatten {
+            let ignored = [
+                (field.schema_with.is_some(), "`schema_with`"),
+                (!field.metadata.is_empty(), "metadata"),
+                (field.metadata_with.is_some(), "`metadata_with`"),
+                (field.doc.is_some(), "a doc comment"),
+                (!field.constraints.is_empty(), "constraints"),
+                (field.nullable, "`nullable`"),
+                (field.enum_values.is_some(), "`enum_values`"),
+            ];
+            if let Some((_, what)) = ignored.into_iter().find(|(set, _)| *set) {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    format!("a flattened field can't have {}", what),
+                ));
+            }
+        }
 
         Ok(field)
     }
 }
+
+/// The strings in `enum_values("a", "b", ...)`. Typedef enums can't be empty
+/// or have duplicates.
+fn parse_enum_values(meta: &Meta) -> Result<Vec<String>, syn::Error> {
+    let Meta::List(l) = meta else {
+        return Err(syn::Error::new_spanned(
+            meta,
+            "expected something like `enum_values(\"a\", \"b\")`",
+        ));
+    };
+    if l.nested.is_empty() {
+        return Err(syn::Error::new_spanned(
+            meta,
+            "`enum_values` needs at least one value",
+        ));
+    }
+
+    let mut values: Vec<String> = vec![];
+    l.nested
+        .iter()
+        .map(|nested| match nested {
+            NestedMeta::Lit(Lit::Str(s)) if values.contains(&s.value()) => Err(
+                syn::Error::new_spanned(s, format!("\"{}\" is listed twice", s.value())),
+            ),
+            NestedMeta::Lit(Lit::Str(s)) => {
+                values.push(s.value());
+                Ok(())
+            }
+            nested => Err(syn::Error::new_spanned(nested, "expected a string literal")),
+        })
+        .collect_fallible::<()>()?;
+
+    Ok(values)
+}
+
+/// Mirrors how the generator parses versions at runtime.
+fn is_valid_version(version: &str) -> bool {
+    version.split('.').all(|part| part.parse::<u64>().is_ok())
+}
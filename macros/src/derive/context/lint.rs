@@ -0,0 +1,94 @@
+use syn::{Attribute, Data, DeriveInput};
+
+use super::{collect_attrs, SERDE_ATTR_IDENT};
+
+/// Serde container attributes we either account for or that don't affect
+/// the JSON.
+const CONTAINER_ATTRS: &[&str] = &[
+    "rename",
+    "rename_all",
+    "deny_unknown_fields",
+    "tag",
+    "transparent",
+    "from",
+    "try_from",
+    "default",
+    "bound",
+    "crate",
+    "expecting",
+];
+
+/// Like [`CONTAINER_ATTRS`], but for variants.
+// `alias` isn't here - the schema only knows the main name, so aliased input
+// would be rejected in deserialize mode
+const VARIANT_ATTRS: &[&str] = &["rename", "other", "bound"];
+
+/// Like [`CONTAINER_ATTRS`], but for fields.
+const FIELD_ATTRS: &[&str] = &[
+    "rename",
+    "skip",
+    "skip_serializing",
+    "skip_deserializing",
+    "skip_serializing_if",
+    "default",
+    "flatten",
+    "with",
+    "serialize_with",
+    "deserialize_with",
+    "borrow",
+    "bound",
+    "getter",
+];
+
+/// With `#[typedef(strict)]`, fail on serde attributes jtd-derive doesn't
+/// understand, since the schema probably doesn't match what serde does.
+/// All of them are reported at once.
+pub fn check_serde_attrs(input: &DeriveInput) -> Result<(), syn::Error> {
+    let mut errors = vec![];
+    let mut check = |attrs: &[Attribute], known: &[&str]| -> Result<(), syn::Error> {
+        for meta in collect_attrs(attrs, SERDE_ATTR_IDENT)? {
+            let path = meta.path();
+            let name = path
+                .get_ident()
+                .map(ToString::to_string)
+                .unwrap_or_default();
+            if !known.contains(&name.as_str()) {
+                errors.push(syn::Error::new_spanned(
+                    path,
+                    format!(
+                        "jtd-derive doesn't account for `#[serde({name})]`, so the schema \
+                         may not match the JSON; remove `#[typedef(strict)]` to allow it"
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    };
+
+    check(&input.attrs, CONTAINER_ATTRS)?;
+    match &input.data {
+        Data::Struct(s) => {
+            for field in &s.fields {
+                check(&field.attrs, FIELD_ATTRS)?;
+            }
+        }
+        Data::Enum(e) => {
+            for variant in &e.variants {
+                check(&variant.attrs, VARIANT_ATTRS)?;
+                for field in &variant.fields {
+                    check(&field.attrs, FIELD_ATTRS)?;
+                }
+            }
+        }
+        Data::Union(_) => {}
+    }
+
+    let mut errors = errors.into_iter();
+    match errors.next() {
+        Some(mut first) => {
+            first.extend(errors);
+            Err(first)
+        }
+        None => Ok(()),
+    }
+}
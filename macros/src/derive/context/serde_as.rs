@@ -0,0 +1,157 @@
+//! `serde_with` adapters, which `#[serde_as]` turns into
+//! `#[serde(with = "::serde_with::As::<Adapter>")]`. The common ones are
+//! translated into a type with the same schema, as if the field had
+//! `#[typedef(as = "...")]`.
+
+use quote::quote;
+use syn::{GenericArgument, LitStr, Path, PathArguments, Type, TypePath};
+
+use super::constraints::Constraint;
+
+/// The type whose schema describes what the `serde_with::As` adapter in
+/// `with` makes of a field of type `ty`, along with any extra metadata.
+/// `None` if `with` isn't a `serde_with::As` path.
+pub fn adapter_schema(
+    with: &LitStr,
+    ty: &Type,
+) -> Result<Option<(Type, Vec<Constraint>)>, syn::Error> {
+    let path: Path = with.parse()?;
+    let is_serde_with =
+        path.segments.len() >= 2 && path.segments[path.segments.len() - 2].ident == "serde_with";
+    let Some(last) = path.segments.last() else {
+        return Ok(None);
+    };
+    if !is_serde_with || last.ident != "As" {
+        return Ok(None);
+    }
+
+    let adapter = match generic_types(&last.arguments).as_slice() {
+        [adapter] => *adapter,
+        _ => return Ok(None),
+    };
+
+    let mut constraints = vec![];
+    match translate(adapter, ty, &mut constraints) {
+        Some(ty) => Ok(Some((ty, constraints))),
+        None => Err(syn::Error::new_spanned(
+            with,
+            "jtd-derive doesn't know what this `serde_with` adapter produces; \
+             specify it with `#[typedef(as = \"...\")]` or \
+             `#[typedef(schema_with = \"...\")]`",
+        )),
+    }
+}
+
+fn translate(adapter: &Type, ty: &Type, constraints: &mut Vec<Constraint>) -> Option<Type> {
+    let path = match adapter {
+        Type::Infer(_) => return Some(ty.clone()),
+        Type::Array(adapter) => {
+            let Type::Array(arr) = ty else { return None };
+            let mut arr = arr.clone();
+            *arr.elem = translate(&adapter.elem, &arr.elem, constraints)?;
+            return Some(Type::Array(arr));
+        }
+        Type::Path(TypePath { qself: None, path }) => path,
+        _ => return None,
+    };
+    let last = path.segments.last()?;
+    let args = generic_types(&last.arguments);
+
+    // the numeric format of durations and timestamps is the first argument,
+    // defaulting to a 64-bit integer, which Typedef can only fit in a float
+    let numeric = |default: Type| -> Type {
+        match args.first() {
+            Some(Type::Path(p)) if p.path.is_ident("String") => syn::parse_quote! { String },
+            Some(Type::Path(p)) if p.path.is_ident("f64") => syn::parse_quote! { f64 },
+            _ => default,
+        }
+    };
+
+    Some(match last.ident.to_string().as_str() {
+        "Same" => ty.clone(),
+        "DisplayFromStr" | "StringWithSeparator" | "NoneAsEmptyString" => {
+            syn::parse_quote! { String }
+        }
+        "BoolFromInt" => syn::parse_quote! { u8 },
+        "Bytes" | "BytesOrString" => syn::parse_quote! { Vec<u8> },
+        "Base64" => {
+            constraints.push(content_encoding("base64"));
+            syn::parse_quote! { String }
+        }
+        "Hex" => {
+            constraints.push(content_encoding("base16"));
+            syn::parse_quote! { String }
+        }
+        "DurationSeconds"
+        | "DurationMilliSeconds"
+        | "DurationMicroSeconds"
+        | "DurationNanoSeconds"
+        | "TimestampSeconds"
+        | "TimestampMilliSeconds"
+        | "TimestampMicroSeconds"
+        | "TimestampNanoSeconds"
+        | "DurationSecondsWithFrac"
+        | "DurationMilliSecondsWithFrac"
+        | "DurationMicroSecondsWithFrac"
+        | "DurationNanoSecondsWithFrac"
+        | "TimestampSecondsWithFrac"
+        | "TimestampMilliSecondsWithFrac"
+        | "TimestampMicroSecondsWithFrac"
+        | "TimestampNanoSecondsWithFrac" => numeric(syn::parse_quote! { f64 }),
+        "DefaultOnError" | "DefaultOnNull" => match args.as_slice() {
+            [] => ty.clone(),
+            [inner] => translate(inner, ty, constraints)?,
+            _ => return None,
+        },
+        // containers of adapters, like `Vec<DisplayFromStr>` for a `Vec<u32>`
+        name => {
+            let Type::Path(TypePath { qself: None, path }) = ty else {
+                return None;
+            };
+            let mut path = path.clone();
+            let ty_last = path.segments.last_mut()?;
+            if ty_last.ident != name {
+                return None;
+            }
+            let PathArguments::AngleBracketed(ty_args) = &mut ty_last.arguments else {
+                return None;
+            };
+            let mut ty_args: Vec<_> = ty_args
+                .args
+                .iter_mut()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect();
+            if ty_args.len() != args.len() {
+                return None;
+            }
+            for (ty_arg, adapter_arg) in ty_args.iter_mut().zip(args) {
+                **ty_arg = translate(adapter_arg, ty_arg, constraints)?;
+            }
+            Type::Path(TypePath { qself: None, path })
+        }
+    })
+}
+
+fn content_encoding(encoding: &str) -> Constraint {
+    Constraint {
+        key: "contentEncoding",
+        value: quote! { #encoding },
+    }
+}
+
+fn generic_types(args: &PathArguments) -> Vec<&Type> {
+    match args {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
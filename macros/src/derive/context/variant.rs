@@ -0,0 +1,122 @@
+use syn::{Lit, Meta, MetaNameValue, Variant};
+
+use super::{collect_attrs, Deprecation, ATTR_IDENT, SERDE_ATTR_IDENT};
+use crate::iter_ext::IterExt as _;
+
+#[derive(Default)]
+pub struct VariantCtx {
+    pub catch_all: bool,
+    /// The enum value or mapping key, overriding the variant name and any
+    /// `rename_all`.
+    pub rename: Option<String>,
+    /// Like `rename`, but for serialization.
+    pub rename_serialize: Option<String>,
+    pub deprecated: Option<Deprecation>,
+    /// Whether the variant has `#[serde(other)]`.
+    pub serde_other: bool,
+    /// Overrides the container's `deny_unknown_fields` for this variant.
+    pub deny_unknown_fields: Option<bool>,
+}
+
+impl VariantCtx {
+    pub fn from_input(input: &Variant) -> Result<Self, syn::Error> {
+        let mut variant = Self {
+            deprecated: super::find_deprecation(&input.attrs)?,
+            ..Self::default()
+        };
+
+        let params = collect_attrs(&input.attrs, ATTR_IDENT)?;
+        params
+            .map(|p| {
+                match p
+                    .path()
+                    .get_ident()
+                    .ok_or_else(|| {
+                        syn::Error::new_spanned(p.path(), "jtd-derive parameter must be an ident")
+                    })?
+                    .to_string()
+                    .as_str()
+                {
+                    "deprecated" => {
+                        variant.deprecated = Some(super::parse_deprecation(&p)?);
+                        Ok(())
+                    }
+                    param @ ("deny_unknown_fields" | "allow_unknown_fields") => {
+                        if let Meta::Path(_) = p {
+                            variant.deny_unknown_fields = Some(param == "deny_unknown_fields");
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                format!("the `{}` parameter takes no value", param),
+                            ))
+                        }
+                    }
+                    "additional_properties" => {
+                        if let Meta::NameValue(MetaNameValue {
+                            lit: Lit::Bool(b), ..
+                        }) = &p
+                        {
+                            variant.deny_unknown_fields = Some(!b.value);
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected `additional_properties = true` or `additional_properties = false`",
+                            ))
+                        }
+                    }
+                    "catch_all" => {
+                        if let Meta::Path(_) = p {
+                            variant.catch_all = true;
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "the `catch_all` parameter takes no value",
+                            ))
+                        }
+                    }
+                    "rename" => {
+                        if let Meta::NameValue(MetaNameValue {
+                            lit: Lit::Str(s), ..
+                        }) = &p
+                        {
+                            variant.rename = Some(s.value());
+                            variant.rename_serialize = Some(s.value());
+                            Ok(())
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                p,
+                                "expected something like `rename = \"...\"`",
+                            ))
+                        }
+                    }
+                    _ => Err(syn::Error::new_spanned(
+                        p.path(),
+                        "unknown jtd-derive parameter",
+                    )),
+                }
+            })
+            .collect_fallible::<()>()?;
+
+        for meta in collect_attrs(&input.attrs, SERDE_ATTR_IDENT)? {
+            // the variant unknown tags are deserialized into
+            if meta.path().is_ident("other") {
+                variant.catch_all = true;
+                variant.serde_other = true;
+            }
+
+            // `#[typedef(rename = "...")]` takes precedence
+            let (ser, de) = super::parse_serde_rename(&meta);
+            if let (Some(name), None) = (ser, &variant.rename_serialize) {
+                variant.rename_serialize = Some(name);
+            }
+            if let (Some(name), None) = (de, &variant.rename) {
+                variant.rename = Some(name);
+            }
+        }
+
+        Ok(variant)
+    }
+}
@@ -1,23 +1,84 @@
 use std::collections::HashMap;
 
-use syn::Type;
+use syn::ext::IdentExt as _;
+use syn::{Path, Type};
 
-use super::context::FieldCtx;
+use super::context::constraints::Constraint;
+use super::context::{Deprecation, FieldCtx, MetadataMerge};
 
 pub struct Field {
+    /// The type to generate the schema for. This is the declared type unless
+    /// overridden with `#[typedef(as = "...")]`.
     pub ty: Type,
     pub ident: String,
     pub meta: HashMap<String, String>,
+    pub metadata_merge: MetadataMerge,
+    pub rename: Option<String>,
+    pub rename_serialize: Option<String>,
+    pub fuzz_hint: Option<String>,
+    pub constraints: Vec<Constraint>,
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub skip_serializing: bool,
+    pub skip_deserializing: bool,
+    pub skip_serializing_if: bool,
+    pub default: bool,
+    pub flatten: bool,
+    pub schema_with: Option<Path>,
+    pub doc: Option<String>,
+    pub nullable: bool,
+    pub deprecated: Option<Deprecation>,
+    pub metadata_with: Option<Path>,
+    pub enum_values: Option<Vec<String>>,
+    pub inline: bool,
+    pub by_ref: bool,
 }
 
 impl Field {
     pub fn from_syn_field(f: &syn::Field) -> Result<Self, syn::Error> {
         let ctx = FieldCtx::from_input(f)?;
+        // `PhantomData` holds no data, so there's nothing to describe
+        let phantom =
+            ctx.schema_as.is_none() && ctx.schema_with.is_none() && is_phantom_data(&f.ty);
 
         Ok(Self {
-            ty: f.ty.clone(),
-            ident: f.ident.as_ref().map(|i| i.to_string()).unwrap(),
+            ty: ctx.schema_as.unwrap_or_else(|| f.ty.clone()),
+            // serde names `r#type` "type"
+            ident: f.ident.as_ref().map(|i| i.unraw().to_string()).unwrap(),
             meta: ctx.metadata,
+            metadata_merge: ctx.metadata_merge,
+            rename: ctx.rename,
+            rename_serialize: ctx.rename_serialize,
+            fuzz_hint: ctx.fuzz_hint,
+            constraints: ctx.constraints,
+            since: ctx.since,
+            until: ctx.until,
+            skip_serializing: ctx.skip_serializing || phantom,
+            skip_deserializing: ctx.skip_deserializing || phantom,
+            skip_serializing_if: ctx.skip_serializing_if,
+            default: ctx.default,
+            flatten: ctx.flatten,
+            schema_with: ctx.schema_with,
+            doc: ctx.doc,
+            nullable: ctx.nullable,
+            deprecated: ctx.deprecated,
+            metadata_with: ctx.metadata_with,
+            enum_values: ctx.enum_values,
+            inline: ctx.inline,
+            by_ref: ctx.by_ref,
         })
     }
 }
+
+/// Whether the type is `PhantomData<...>`, as far as we can tell from its path.
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|seg| seg.ident == "PhantomData")
+            .unwrap_or_default(),
+        _ => false,
+    }
+}
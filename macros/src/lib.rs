@@ -7,16 +7,59 @@
 
 mod derive;
 pub(crate) mod iter_ext;
+mod module;
 
-use quote::ToTokens;
-use syn::{parse_macro_input, DeriveInput};
+use syn::{parse_macro_input, DeriveInput, ItemMod};
 
-#[proc_macro_derive(JsonTypedef, attributes(typedef))]
+// With the `serde_valid` or `validator` feature, we also declare `validate` as
+// a helper attribute, and with `garde`, `garde`. They're normally declared by
+// the `Validate` derives of those crates, but this way the attributes also work
+// when those derives aren't there.
+#[cfg_attr(
+    all(
+        not(any(feature = "serde_valid", feature = "validator")),
+        not(feature = "garde")
+    ),
+    proc_macro_derive(JsonTypedef, attributes(typedef))
+)]
+#[cfg_attr(
+    all(
+        any(feature = "serde_valid", feature = "validator"),
+        not(feature = "garde")
+    ),
+    proc_macro_derive(JsonTypedef, attributes(typedef, validate))
+)]
+#[cfg_attr(
+    all(
+        not(any(feature = "serde_valid", feature = "validator")),
+        feature = "garde"
+    ),
+    proc_macro_derive(JsonTypedef, attributes(typedef, garde))
+)]
+#[cfg_attr(
+    all(any(feature = "serde_valid", feature = "validator"), feature = "garde"),
+    proc_macro_derive(JsonTypedef, attributes(typedef, validate, garde))
+)]
 pub fn query_responses_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
     let expanded = match derive::derive(input) {
-        Ok(item_impl) => item_impl.into_token_stream(),
+        Ok(tokens) => tokens,
+        Err(e) => e.into_compile_error(),
+    };
+
+    proc_macro::TokenStream::from(expanded)
+}
+
+#[proc_macro_attribute]
+pub fn typedef_module(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ItemMod);
+
+    let expanded = match module::typedef_module(args.into(), input) {
+        Ok(tokens) => tokens,
         Err(e) => e.into_compile_error(),
     };
 
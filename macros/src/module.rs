@@ -0,0 +1,95 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Attribute, Item, ItemMod, Meta, NestedMeta};
+
+/// Derive `JsonTypedef` for the structs and enums directly in the module
+/// that don't already, and add a `register_schemas` function to the module
+/// that registers the non-generic ones.
+pub fn typedef_module(args: TokenStream, mut module: ItemMod) -> Result<TokenStream, syn::Error> {
+    if !args.is_empty() {
+        return Err(syn::Error::new_spanned(
+            args,
+            "#[typedef_module] takes no arguments",
+        ));
+    }
+
+    let Some((_, items)) = &mut module.content else {
+        return Err(syn::Error::new_spanned(
+            &module,
+            "#[typedef_module] only works on modules with inline content",
+        ));
+    };
+
+    let mut registered = vec![];
+    for item in items.iter_mut() {
+        let (attrs, ident, generics) = match item {
+            Item::Struct(s) => (&mut s.attrs, &s.ident, &s.generics),
+            Item::Enum(e) => (&mut e.attrs, &e.ident, &e.generics),
+            _ => continue,
+        };
+
+        // `#[typedef(skip)]` opts a type out
+        let len = attrs.len();
+        attrs.retain(|attr| !is_skip(attr));
+        if attrs.len() != len {
+            continue;
+        }
+
+        if !attrs.iter().any(derives_json_typedef) {
+            attrs.insert(0, parse_quote! { #[derive(::jtd_derive::JsonTypedef)] });
+        }
+        if generics.params.is_empty() {
+            registered.push(ident.clone());
+        }
+    }
+
+    items.push(parse_quote! {
+        /// Register the schemas of the non-generic types in this module,
+        /// generated with `Generator::default()`.
+        #[allow(dead_code)]
+        pub fn register_schemas(
+            registry: &mut ::jtd_derive::registry::SchemaRegistry,
+        ) -> ::std::result::Result<
+            &mut ::jtd_derive::registry::SchemaRegistry,
+            ::jtd_derive::GenError,
+        > {
+            #(registry.register::<#registered>()?;)*
+            ::std::result::Result::Ok(registry)
+        }
+    });
+
+    Ok(quote! { #module })
+}
+
+fn is_skip(attr: &Attribute) -> bool {
+    if !attr.path.is_ident("typedef") {
+        return false;
+    }
+
+    match attr.parse_meta() {
+        Ok(Meta::List(l)) => {
+            l.nested.len() == 1
+                && matches!(&l.nested[0], NestedMeta::Meta(Meta::Path(p)) if p.is_ident("skip"))
+        }
+        _ => false,
+    }
+}
+
+fn derives_json_typedef(attr: &Attribute) -> bool {
+    if !attr.path.is_ident("derive") {
+        return false;
+    }
+
+    match attr.parse_meta() {
+        Ok(Meta::List(l)) => l.nested.iter().any(|nested| match nested {
+            NestedMeta::Meta(meta) => meta
+                .path()
+                .segments
+                .last()
+                .map(|seg| seg.ident == "JsonTypedef")
+                .unwrap_or_default(),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
@@ -0,0 +1,73 @@
+//! Implementation details used by code generated by `jtd-derive-macros`.
+//! Not part of the public API.
+
+use crate::schema::Schema;
+use crate::Names;
+
+/// Names for a type parameter of a derived type that the schema doesn't
+/// depend on, so it isn't bound by `JsonTypedef`.
+///
+/// There are no `JsonTypedef` names to use, so this falls back on
+/// `type_name`. Its output isn't stable between compiler versions, which
+/// `tests/snapshot.rs` is there to notice.
+pub fn type_param_names<T: ?Sized>() -> Names {
+    let name = std::any::type_name::<T>();
+    Names {
+        short: name,
+        long: name,
+        path_segments: vec![name],
+        nullable: false,
+        type_params: vec![],
+        const_params: vec![],
+    }
+}
+
+/// How a const generic argument appears in [`Names`]. Const parameters can
+/// be integers, `bool`s or `char`s - rendering them the way they'd be
+/// written in Rust keeps chars like `,` or `>` from garbling names.
+pub fn const_param_name(value: impl std::fmt::Debug) -> String {
+    format!("{:?}", value)
+}
+
+/// The schema of a `#[typedef(enum_values(...))]` field. It only narrows
+/// down which strings the field takes, so it's nullable (like an
+/// `Option<String>`) and keeps metadata if the field's type does.
+pub fn string_enum(schema: Schema, values: &[&'static str]) -> Schema {
+    Schema {
+        ty: Schema::enumeration(values.iter().copied()).ty,
+        ..schema
+    }
+}
+
+#[cfg(feature = "inventory")]
+pub use inventory;
+pub use serde_json;
+
+/// Add metadata entries given as JSON text, as the derive macro renders
+/// `#[typedef(metadata(...))]` and `#[typedef(metadata_json = "...")]`
+/// attributes.
+pub fn with_metadata(mut schema: Schema, entries: &[(&'static str, &str)]) -> Schema {
+    schema.metadata.extend(
+        entries
+            .iter()
+            .map(|(key, value)| (*key, parse_metadata(value))),
+    );
+    schema
+}
+
+/// Like [`with_metadata`], but keys the schema already has are left alone.
+pub fn with_metadata_preserving(mut schema: Schema, entries: &[(&'static str, &str)]) -> Schema {
+    let entries: Vec<_> = entries
+        .iter()
+        .filter(|(key, _)| schema.metadata.get(key).is_none())
+        .map(|(key, value)| (*key, parse_metadata(value)))
+        .collect();
+    schema.metadata.extend(entries);
+    schema
+}
+
+fn parse_metadata(value: &str) -> serde_json::Value {
+    value
+        .parse()
+        .expect("the derive macro only emits valid JSON metadata")
+}
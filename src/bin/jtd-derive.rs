@@ -0,0 +1,149 @@
+//! Command line tooling for _JSON Typedef_ schema files, like the ones exported
+//! by projects using `jtd-derive`.
+//!
+//! Only built with the `cli` feature enabled.
+
+use std::collections::BTreeSet;
+use std::process::ExitCode;
+
+use serde_json::Value;
+
+const USAGE: &str = "\
+Usage: jtd-derive <COMMAND> [ARGS]
+
+Commands:
+  validate <SCHEMA> <INSTANCE>...  validate JSON instances against a schema
+  diff <OLD> <NEW>                 show differences between two schemas
+  json-schema <SCHEMA>             convert a schema to JSON Schema (draft 7)
+  fmt <SCHEMA>                     pretty-print a schema
+  help                             print this message";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["validate", schema, instances @ ..] if !instances.is_empty() => {
+            validate(schema, instances)
+        }
+        ["diff", old, new] => diff(old, new),
+        ["json-schema", schema] => json_schema(schema),
+        ["fmt", schema] => fmt(schema),
+        ["help" | "--help" | "-h"] => {
+            println!("{}", USAGE);
+            Ok(true)
+        }
+        _ => Err(format!("invalid arguments\n\n{}", USAGE)),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn read_json(path: &str) -> Result<Value, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn read_schema(path: &str) -> Result<jtd::Schema, String> {
+    let schema = read_json(path)?;
+    jtd_derive::schema::verify_json(&schema).map_err(|e| format!("{}: {}", path, e))?;
+    let schema = serde_json::from_value(schema).map_err(|e| format!("{}: {}", path, e))?;
+    let schema =
+        jtd::Schema::from_serde_schema(schema).map_err(|e| format!("{}: {:?}", path, e))?;
+    schema
+        .validate()
+        .map_err(|e| format!("{}: invalid schema: {:?}", path, e))?;
+
+    Ok(schema)
+}
+
+/// Returns `Ok(false)` if any instance failed validation.
+fn validate(schema: &str, instances: &[&str]) -> Result<bool, String> {
+    let schema = read_schema(schema)?;
+    let mut all_valid = true;
+
+    for path in instances {
+        let instance = read_json(path)?;
+        let errors = jtd::validate(&schema, &instance, jtd::ValidateOptions::new())
+            .map_err(|e| format!("{}: {:?}", path, e))?;
+
+        if errors.is_empty() {
+            println!("{}: ok", path);
+        }
+
+        for error in errors {
+            all_valid = false;
+            let (instance_path, schema_path) = error.into_owned_paths();
+            println!(
+                "{}: /{} rejected by /{}",
+                path,
+                instance_path.join("/"),
+                schema_path.join("/")
+            );
+        }
+    }
+
+    Ok(all_valid)
+}
+
+/// Returns `Ok(false)` if the schemas differ.
+fn diff(old: &str, new: &str) -> Result<bool, String> {
+    let (old, new) = (read_json(old)?, read_json(new)?);
+    let mut changes = vec![];
+    diff_values(String::new(), &old, &new, &mut changes);
+
+    for change in &changes {
+        println!("{}", change);
+    }
+
+    Ok(changes.is_empty())
+}
+
+fn diff_values(path: String, old: &Value, new: &Value, changes: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            let keys: BTreeSet<_> = old.keys().chain(new.keys()).collect();
+
+            for key in keys {
+                // escaped the JSON Pointer way, so that `/` in property names
+                // doesn't look like nesting
+                let path = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                match (old.get(key), new.get(key)) {
+                    (Some(old), Some(new)) => diff_values(path, old, new, changes),
+                    (Some(old), None) => changes.push(format!("- {}: {}", path, old)),
+                    (None, Some(new)) => changes.push(format!("+ {}: {}", path, new)),
+                    (None, None) => unreachable!(),
+                }
+            }
+        }
+        (old, new) if old != new => {
+            let path = if path.is_empty() { "/" } else { &path };
+            changes.push(format!("~ {}: {} -> {}", path, old, new));
+        }
+        _ => {}
+    }
+}
+
+fn json_schema(schema: &str) -> Result<bool, String> {
+    read_schema(schema)?;
+    let json_schema = jtd_derive::json_schema::from_jtd(&read_json(schema)?);
+    print_pretty(&json_schema)
+}
+
+fn fmt(schema: &str) -> Result<bool, String> {
+    read_schema(schema)?;
+    print_pretty(&read_json(schema)?)
+}
+
+fn print_pretty(value: &Value) -> Result<bool, String> {
+    let pretty = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    println!("{}", pretty);
+    Ok(true)
+}
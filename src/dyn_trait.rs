@@ -0,0 +1,147 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::schema::Schema;
+use crate::type_id::{type_id, TypeId};
+use crate::{Generator, JsonTypedef, Names};
+
+/// An object-safe counterpart of [`JsonTypedef`], for when schema providers
+/// need to be stored as trait objects, e.g. in a plugin system.
+///
+/// Every [`JsonTypedef`] type implements this trait, so a value can be boxed
+/// directly. When there's no value at hand, use [`TypeOf`] instead.
+///
+/// Schemas are generated from trait objects with
+/// [`Generator::into_root_schema_dyn`] and [`Generator::sub_schema_dyn`].
+///
+/// # Example
+///
+/// ```
+/// use jtd_derive::{Generator, JsonTypedef, JsonTypedefDyn, TypeOf};
+///
+/// #[derive(JsonTypedef)]
+/// struct Foo {
+///     x: u32,
+/// }
+///
+/// #[derive(JsonTypedef)]
+/// struct Bar {
+///     y: bool,
+/// }
+///
+/// let providers: Vec<Box<dyn JsonTypedefDyn>> = vec![
+///     Box::new(Foo { x: 5 }),
+///     Box::new(TypeOf::<Bar>::new()),
+/// ];
+///
+/// for provider in &providers {
+///     Generator::default()
+///         .into_root_schema_dyn(provider.as_ref())
+///         .unwrap();
+/// }
+/// ```
+///
+/// # Implementing
+///
+/// Types that can't implement [`JsonTypedef`], e.g. because their schema is
+/// only known at runtime, can implement this trait directly. Definitions are
+/// identified by the implementing type together with [`dyn_names`](Self::dyn_names),
+/// so values of one such type that describe different schemas must also
+/// report different names.
+pub trait JsonTypedefDyn {
+    /// Generate the [`Schema`]. See [`JsonTypedef::schema`].
+    fn dyn_schema(&self, generator: &mut Generator) -> Schema;
+
+    /// See [`JsonTypedef::referenceable`].
+    fn dyn_referenceable(&self) -> bool {
+        true
+    }
+
+    /// See [`JsonTypedef::names`].
+    fn dyn_names(&self) -> Names;
+
+    /// See [`JsonTypedef::source_location`].
+    fn dyn_source_location(&self) -> Option<(&'static str, u32)> {
+        None
+    }
+
+    #[doc(hidden)]
+    fn dyn_type_id(&self) -> TypeId {
+        TypeId::new(std::any::type_name::<Self>(), self.dyn_names())
+    }
+}
+
+impl<T: JsonTypedef> JsonTypedefDyn for T {
+    fn dyn_schema(&self, generator: &mut Generator) -> Schema {
+        T::schema(generator)
+    }
+
+    fn dyn_referenceable(&self) -> bool {
+        T::referenceable()
+    }
+
+    fn dyn_names(&self) -> Names {
+        T::names()
+    }
+
+    fn dyn_source_location(&self) -> Option<(&'static str, u32)> {
+        T::source_location()
+    }
+
+    fn dyn_type_id(&self) -> TypeId {
+        type_id::<T>()
+    }
+}
+
+/// A [`JsonTypedefDyn`] provider for the type `T` that doesn't require a value
+/// of that type.
+pub struct TypeOf<T: ?Sized>(PhantomData<fn() -> Box<T>>);
+
+impl<T: ?Sized> TypeOf<T> {
+    /// Create a provider for `T`.
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: ?Sized> Default for TypeOf<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized> Clone for TypeOf<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for TypeOf<T> {}
+
+impl<T: ?Sized> fmt::Debug for TypeOf<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TypeOf<{}>", std::any::type_name::<T>())
+    }
+}
+
+impl<T: JsonTypedef + ?Sized> JsonTypedefDyn for TypeOf<T> {
+    fn dyn_schema(&self, generator: &mut Generator) -> Schema {
+        T::schema(generator)
+    }
+
+    fn dyn_referenceable(&self) -> bool {
+        T::referenceable()
+    }
+
+    fn dyn_names(&self) -> Names {
+        T::names()
+    }
+
+    fn dyn_source_location(&self) -> Option<(&'static str, u32)> {
+        T::source_location()
+    }
+
+    fn dyn_type_id(&self) -> TypeId {
+        type_id::<T>()
+    }
+}
@@ -1,14 +1,21 @@
 //! Schema generator and its settings.
 
+mod config;
 mod naming_strategy;
+mod property_case;
 
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
 
-use self::naming_strategy::NamingStrategy;
-use crate::schema::{RootSchema, Schema, SchemaType};
+use serde::{Deserialize, Serialize};
+
+pub use self::config::{GeneratorConfig, InliningMode, NamingPreset};
+pub use self::naming_strategy::{IntoName, NamingContext, NamingStrategy};
+pub use self::property_case::PropertyCase;
+use crate::schema::{PropertiesBuilder, RootSchema, Schema, SchemaType, TypeSchema};
 use crate::type_id::{type_id, TypeId};
-use crate::{JsonTypedef, Names};
+use crate::{JsonTypedef, JsonTypedefDyn, Names, TypeOf};
 
 /// A configurable schema generator. An instance is meant to produce one
 /// [`RootSchema`] and be consumed in the process.
@@ -70,7 +77,7 @@ use crate::{JsonTypedef, Names};
 ///     "ref": "Foo",
 /// } });
 /// ```
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct Generator {
     naming_strategy: NamingStrategy,
     /// Types for which at least one ref was created during schema gen.
@@ -78,9 +85,66 @@ pub struct Generator {
     refs: HashSet<TypeId>,
     definitions: HashMap<TypeId, (Names, DefinitionState)>,
     inlining: Inlining,
+    property_case: Option<PropertyCase>,
+    /// The type the root schema is being generated for.
+    root: Option<TypeId>,
+    /// Types whose schemas are currently being generated, outermost first.
+    stack: Vec<(TypeId, Names)>,
+    /// Set while a flattened, `#[typedef(inline)]` or `#[typedef(ref)]` field
+    /// is being generated. The next referenceable type is then inlined
+    /// (`Some(true)`) or provided by ref (`Some(false)`) no matter what.
+    /// Flattening needs its properties.
+    force_inline: Option<bool>,
+    max_depth: usize,
+    byte_sequences: ByteSequences,
+    integers_64: Integers64,
+    integers_128: Integers128,
+    char_length: bool,
+    deny_non_string_keys: bool,
+    enum_keys_as_properties: bool,
+    source_locations: bool,
+    descriptions: bool,
+    mode: SchemaMode,
+    version: Option<Version>,
+    metadata_namespace: Option<Cow<'static, str>>,
+    /// The first error encountered while generating subschemas. Those can't
+    /// fail directly, so this is reported at the end.
+    error: Option<GenError>,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self {
+            naming_strategy: NamingStrategy::default(),
+            refs: HashSet::default(),
+            definitions: HashMap::default(),
+            inlining: Inlining::default(),
+            property_case: None,
+            root: None,
+            stack: vec![],
+            force_inline: None,
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            byte_sequences: ByteSequences::default(),
+            integers_64: Integers64::default(),
+            integers_128: Integers128::default(),
+            char_length: true,
+            deny_non_string_keys: false,
+            enum_keys_as_properties: false,
+            source_locations: false,
+            descriptions: false,
+            mode: SchemaMode::default(),
+            version: None,
+            metadata_namespace: None,
+            error: None,
+        }
+    }
 }
 
 impl Generator {
+    /// The default limit of how deeply types can be nested. See
+    /// [`GeneratorBuilder::max_depth`].
+    pub const DEFAULT_MAX_DEPTH: usize = 128;
+
     /// Provide a `Generator` builder, allowing for some customization.
     pub fn builder() -> GeneratorBuilder {
         GeneratorBuilder::default()
@@ -90,45 +154,75 @@ impl Generator {
     /// This consumes the generator.
     ///
     /// This will return an error if a naming collision is detected, i.e. two
-    /// distinct Rust types produce the same identifier.
-    pub fn into_root_schema<T: JsonTypedef>(mut self) -> Result<RootSchema, GenError> {
-        let schema = self.sub_schema_impl::<T>(true);
+    /// distinct Rust types produce the same identifier, or if a type contains
+    /// itself in a way _Typedef_ can't express. See [`GenError`] for details.
+    pub fn into_root_schema<T: JsonTypedef>(self) -> Result<RootSchema, GenError> {
+        self.into_root_schema_dyn(&TypeOf::<T>::new())
+    }
+
+    /// Like [`into_root_schema`](Self::into_root_schema), but for a
+    /// [`JsonTypedefDyn`] trait object.
+    pub fn into_root_schema_dyn(mut self, ty: &dyn JsonTypedefDyn) -> Result<RootSchema, GenError> {
+        self.root = Some(ty.dyn_type_id());
+        let schema = self.sub_schema_impl(ty, true);
+        if let Some(e) = self.error {
+            return Err(e);
+        }
         self.clean_up_defs();
 
-        fn process_defs(
-            defs: HashMap<TypeId, (Names, DefinitionState)>,
-            ns: &mut NamingStrategy,
-        ) -> Result<BTreeMap<String, Schema>, GenError> {
+        fn process_defs(gen: &mut Generator) -> Result<BTreeMap<String, Schema>, GenError> {
             // This could probably be optimized somehow.
 
-            let defs = defs
+            let defs: Vec<_> = std::mem::take(&mut gen.definitions)
                 .into_iter()
-                .map(|(_, (n, s))| (ns.fun()(&n), (n, s.unwrap())));
+                .map(|(id, (n, s))| Ok((gen.name(&id, &n)?, (n, s.unwrap()))))
+                .collect::<Result<_, GenError>>()?;
 
-            let mut map = HashMap::new();
+            let mut map: HashMap<String, (Names, Schema)> = HashMap::new();
 
             for (key, (names, schema)) in defs {
                 if let Some((other_names, _)) = map.get(&key) {
                     return Err(GenError::NameCollision {
                         id: key,
-                        type1: NamingStrategy::long().fun()(other_names),
-                        type2: NamingStrategy::long().fun()(&names),
+                        type1: other_names.to_string(),
+                        type2: names.to_string(),
                     });
                 } else {
                     map.insert(key, (names, schema));
                 }
             }
 
+            check_ref_cycles(&map)?;
+
             Ok(map
                 .into_iter()
                 .map(|(key, (_, schema))| (key, schema))
                 .collect())
         }
 
-        Ok(RootSchema {
-            definitions: process_defs(self.definitions, &mut self.naming_strategy)?,
+        let mut root = RootSchema {
+            definitions: process_defs(&mut self)?,
             schema,
-        })
+        };
+
+        if let Some(case) = self.property_case {
+            case.apply_to_schema(&mut root.schema)?;
+            for schema in root.definitions.values_mut() {
+                case.apply_to_schema(schema)?;
+            }
+        }
+
+        if let Some(namespace) = &self.metadata_namespace {
+            for schema in std::iter::once(&mut root.schema).chain(root.definitions.values_mut()) {
+                schema.visit_mut(&mut |schema| {
+                    schema
+                        .metadata
+                        .move_into_namespace(namespace, EMITTED_METADATA_KEYS)
+                });
+            }
+        }
+
+        Ok(root)
     }
 
     /// Generate a [`Schema`] for a given type, adding definitions to the
@@ -138,11 +232,258 @@ impl Generator {
     /// new types. Most commonly you'll derive that trait. It's unlikely you'll
     /// need to call this method explicitly.
     pub fn sub_schema<T: JsonTypedef + ?Sized>(&mut self) -> Schema {
-        self.sub_schema_impl::<T>(false)
+        self.sub_schema_impl(&TypeOf::<T>::new(), false)
+    }
+
+    /// Like [`sub_schema`](Self::sub_schema), but `T` (or the type it wraps,
+    /// like with `Option<T>` or `Vec<T>`) is inlined rather than provided by
+    /// ref, even if the generator would otherwise use a ref. Other uses of
+    /// the type aren't affected. Recursive types still end up as refs.
+    ///
+    /// This is what `#[typedef(inline)]` on a field does.
+    pub fn sub_schema_inline<T: JsonTypedef + ?Sized>(&mut self) -> Schema {
+        self.force_inline = Some(true);
+        let schema = self.sub_schema::<T>();
+        self.force_inline = None;
+        schema
+    }
+
+    /// Like [`sub_schema`](Self::sub_schema), but `T` (or the type it wraps)
+    /// is provided by ref, even if the generator would otherwise inline it.
+    /// Types that are never referenceable, like primitives, are still inlined.
+    ///
+    /// With [`GeneratorBuilder::prefer_inline`], uses of the type generated
+    /// after this one get the ref too, the same way they do for recursive
+    /// types - there's a definition for it anyway.
+    ///
+    /// This is what `#[typedef(ref)]` on a field does.
+    pub fn sub_schema_ref<T: JsonTypedef + ?Sized>(&mut self) -> Schema {
+        self.force_inline = Some(false);
+        let schema = self.sub_schema::<T>();
+        self.force_inline = None;
+        schema
     }
 
-    fn sub_schema_impl<T: JsonTypedef + ?Sized>(&mut self, top_level: bool) -> Schema {
+    /// Add the properties of `T`'s schema to `props`, the way
+    /// `#[serde(flatten)]` merges a field's fields into its parent. If `T`
+    /// is nullable (like an `Option`), all of them become optional.
+    ///
+    /// Flattening a map (a values schema), the usual way of capturing unknown
+    /// keys, allows additional properties instead.
+    ///
+    /// This fails generation with [`GenError::InvalidFlatten`] if `T`'s
+    /// schema is neither a properties nor a values schema.
+    pub fn flatten_into<T: JsonTypedef + ?Sized>(&mut self, props: &mut PropertiesBuilder) {
         let id = type_id::<T>();
+        // flattened types are always inlined, so one that's still being built
+        // can't be flattened into itself even through a ref
+        let start = match self.definitions.get(&id) {
+            Some((_, DefinitionState::Processing)) => {
+                self.stack.iter().position(|(other, _)| *other == id)
+            }
+            _ => self.cycle_start(&id),
+        };
+        if let Some(start) = start {
+            let chain = self.chain(start, &TypeOf::<T>::new());
+            self.fail(GenError::UnrepresentableCycle { chain });
+            return;
+        }
+
+        self.force_inline = Some(true);
+        let schema = self.sub_schema::<T>();
+        self.force_inline = None;
+
+        match schema.ty {
+            SchemaType::Properties {
+                properties,
+                optional_properties,
+                ..
+            } => {
+                for (name, prop) in properties {
+                    if schema.nullable {
+                        props.optional_property(name, prop);
+                    } else {
+                        props.property(name, prop);
+                    }
+                }
+                for (name, prop) in optional_properties {
+                    props.optional_property(name, prop);
+                }
+            }
+            SchemaType::Values { .. } => {
+                props.additional_properties(true);
+            }
+            _ => self.fail(GenError::InvalidFlatten {
+                type_name: T::names().to_string(),
+            }),
+        }
+    }
+
+    /// Whether doc comments should be copied into `description` metadata.
+    /// See [`GeneratorBuilder::descriptions`].
+    pub fn descriptions(&self) -> bool {
+        self.descriptions
+    }
+
+    /// Whether schemas should describe serialized output or deserialized
+    /// input. See [`GeneratorBuilder::mode`].
+    ///
+    /// Implementors of [`JsonTypedef`] for types that (de)serialize
+    /// asymmetrically should check this.
+    pub fn mode(&self) -> SchemaMode {
+        self.mode
+    }
+
+    /// Whether something available from version `since` (inclusive) until
+    /// version `until` (exclusive) should be part of the schema. Without a
+    /// [`GeneratorBuilder::for_version`] filter, everything is included.
+    ///
+    /// This is what `#[typedef(since = "...", until = "...")]` field attributes
+    /// use. Versions that can't be parsed are ignored.
+    pub fn includes_version(&self, since: Option<&str>, until: Option<&str>) -> bool {
+        let Some(version) = &self.version else {
+            return true;
+        };
+
+        since
+            .and_then(Version::parse)
+            .is_none_or(|since| *version >= since)
+            && until
+                .and_then(Version::parse)
+                .is_none_or(|until| *version < until)
+    }
+
+    /// The schema of a sequence of `T`s. Byte sequences are represented
+    /// according to [`GeneratorBuilder::byte_sequences`].
+    pub(crate) fn sequence_schema<T: JsonTypedef + ?Sized>(&mut self) -> Schema {
+        match self.byte_sequences {
+            ByteSequences::Base64 if type_id::<T>() == type_id::<u8>() => {
+                Schema::of_type(TypeSchema::String).with_metadata("contentEncoding", "base64")
+            }
+            _ => Schema::elements(self.sub_schema::<T>()),
+        }
+    }
+
+    /// The schema of a map from `K`s to `V`s. _Typedef_ has no way to
+    /// describe keys, so unless they're plain strings, the key's schema is
+    /// recorded in a `keySchema` metadata entry. 64-bit and 128-bit integer
+    /// keys are described as strings. Maps keyed by enums can be
+    /// properties schemas instead, see
+    /// [`GeneratorBuilder::enum_keys_as_properties`].
+    pub(crate) fn map_schema<K: JsonTypedef + ?Sized, V: JsonTypedef + ?Sized>(
+        &mut self,
+    ) -> Schema {
+        // keys are strings on the wire, so wide integers are fine there no
+        // matter how they're represented elsewhere
+        let integers = (self.integers_64, self.integers_128);
+        (self.integers_64, self.integers_128) = (Integers64::String, Integers128::String);
+        let key_schema = K::schema(self);
+        (self.integers_64, self.integers_128) = integers;
+        if self.deny_non_string_keys
+            && !matches!(
+                key_schema.ty,
+                SchemaType::Type {
+                    r#type: TypeSchema::String
+                } | SchemaType::Enum { .. }
+            )
+        {
+            self.fail(GenError::NonStringKey {
+                key_type: K::names().to_string(),
+            });
+        }
+
+        let value_schema = self.sub_schema::<V>();
+        if let (true, SchemaType::Enum { r#enum }) = (self.enum_keys_as_properties, &key_schema.ty)
+        {
+            let mut props = Schema::properties();
+            for &key in r#enum {
+                props.optional_property(key, value_schema.clone());
+            }
+            return props.build();
+        }
+
+        let schema = Schema::values(value_schema);
+        if key_schema == Schema::of_type(TypeSchema::String) {
+            schema
+        } else {
+            let key_schema =
+                serde_json::to_value(key_schema).expect("schemas are always serializable");
+            schema.with_metadata("keySchema", key_schema)
+        }
+    }
+
+    /// The schema of a 64-bit or 128-bit integer type, represented according
+    /// to [`GeneratorBuilder::integers_64`] or
+    /// [`GeneratorBuilder::integers_128`].
+    pub(crate) fn wide_integer_schema(
+        &mut self,
+        type_name: &'static str,
+        bits: u32,
+        signed: bool,
+    ) -> Schema {
+        let ty = match (bits, self.integers_64, self.integers_128) {
+            (64, Integers64::Error, _) | (128, _, Integers128::Error) => None,
+            (64, Integers64::Int32, _) if signed => Some(TypeSchema::Int32),
+            (64, Integers64::Int32, _) => Some(TypeSchema::Uint32),
+            (64, Integers64::String, _) | (128, _, Integers128::String) => Some(TypeSchema::String),
+            (128, _, Integers128::Float64) => Some(TypeSchema::Float64),
+            _ => unreachable!("only 64-bit and 128-bit integers are wide"),
+        };
+
+        match ty {
+            Some(ty) => {
+                let format = format!("{}int{}", if signed { "" } else { "u" }, bits);
+                Schema::of_type(ty).with_metadata("format", format)
+            }
+            None => {
+                // the integer type is on top of the stack. it's only listed
+                // there if it's referenceable, like the `NonZero` ones
+                let mut chain: Vec<_> = self
+                    .stack
+                    .iter()
+                    .filter(|(id, _)| self.definitions.contains_key(id))
+                    .map(|(_, names)| names.to_string())
+                    .collect();
+                if !matches!(self.stack.last(), Some((id, _)) if self.definitions.contains_key(id))
+                {
+                    chain.push(type_name.to_string());
+                }
+                self.fail(GenError::UnsupportedInteger {
+                    type_name: type_name.to_string(),
+                    bits,
+                    chain,
+                });
+                Schema::default()
+            }
+        }
+    }
+
+    /// Whether `char` schemas should carry length metadata. See
+    /// [`GeneratorBuilder::omit_char_length`].
+    pub(crate) fn char_length(&self) -> bool {
+        self.char_length
+    }
+
+    /// Override the mode the generator was built with.
+    #[cfg(feature = "testing")]
+    pub(crate) fn set_mode(&mut self, mode: SchemaMode) {
+        self.mode = mode;
+    }
+
+    /// Like [`sub_schema`](Self::sub_schema), but for a [`JsonTypedefDyn`]
+    /// trait object.
+    pub fn sub_schema_dyn(&mut self, ty: &dyn JsonTypedefDyn) -> Schema {
+        self.sub_schema_impl(ty, false)
+    }
+
+    fn sub_schema_impl(&mut self, ty: &dyn JsonTypedefDyn, top_level: bool) -> Schema {
+        let id = ty.dyn_type_id();
+        // wrappers like `Option` pass this on to the type they wrap
+        let force_inline = if ty.dyn_referenceable() {
+            self.force_inline.take()
+        } else {
+            None
+        };
         let inlining = match self.inlining {
             Inlining::Always => true,
             Inlining::Normal => top_level,
@@ -154,8 +495,9 @@ impl Generator {
                 // we had already built a schema for this type.
                 // no need to do it again.
 
-                (!T::referenceable() || (inlining && !self.refs.contains(&id)))
-                    .then_some(schema.clone())
+                (!ty.dyn_referenceable()
+                    || force_inline.unwrap_or(inlining && !self.refs.contains(&id)))
+                .then_some(schema.clone())
             }
             Some((_, DefinitionState::Processing)) => {
                 // we're already in the process of building a schema for this type.
@@ -164,21 +506,54 @@ impl Generator {
 
                 None
             }
+            None if self.cycle_start(&id).is_some() => {
+                // a non-referenceable type contains itself (referenceable ones
+                // would be handled above). there's no way to express that, so
+                // we give up.
+
+                let start = self.cycle_start(&id).unwrap();
+                let chain = self.chain(start, ty);
+                self.fail(GenError::UnrepresentableCycle { chain });
+
+                Some(Schema::default())
+            }
+            None if self.stack.len() >= self.max_depth => {
+                let chain = self.chain(0, ty);
+                self.fail(GenError::DepthLimitExceeded {
+                    limit: self.max_depth,
+                    chain,
+                });
+
+                Some(Schema::default())
+            }
             None => {
                 // no schema available yet, so we have to build it
-                if T::referenceable() {
+                if ty.dyn_referenceable() {
                     self.definitions
-                        .insert(id, (T::names(), DefinitionState::Processing));
-                    let schema = T::schema(self);
+                        .insert(id.clone(), (ty.dyn_names(), DefinitionState::Processing));
+                    self.stack.push((id.clone(), ty.dyn_names()));
+                    let mut schema = ty.dyn_schema(self);
+                    self.stack.pop();
+                    if let (true, Some((file, line))) =
+                        (self.source_locations, ty.dyn_source_location())
+                    {
+                        schema = schema.with_metadata("sourceLocation", format!("{file}:{line}"));
+                    }
                     self.definitions
                         .get_mut(&id)
                         .unwrap()
                         .1
                         .finalize(schema.clone());
 
-                    (inlining && !self.refs.contains(&id)).then_some(schema)
+                    force_inline
+                        .unwrap_or(inlining && !self.refs.contains(&id))
+                        .then_some(schema)
                 } else {
-                    Some(T::schema(self))
+                    self.stack.push((id.clone(), ty.dyn_names()));
+                    let schema = ty.dyn_schema(self);
+                    self.stack.pop();
+
+                    Some(schema)
                 }
             }
         };
@@ -186,7 +561,11 @@ impl Generator {
         inlined_schema.unwrap_or_else(|| {
             let schema = Schema {
                 ty: SchemaType::Ref {
-                    r#ref: self.naming_strategy.fun()(&T::names()),
+                    r#ref: self.name(&id, &ty.dyn_names()).unwrap_or_else(|e| {
+                        let fallback = ty.dyn_names().to_string();
+                        self.fail(e);
+                        fallback
+                    }),
                 },
                 ..Schema::default()
             };
@@ -195,6 +574,41 @@ impl Generator {
         })
     }
 
+    fn name(&self, id: &TypeId, names: &Names) -> Result<String, GenError> {
+        self.naming_strategy.apply_with_context(&NamingContext {
+            names,
+            is_root: self.root.as_ref() == Some(id),
+        })
+    }
+
+    /// Where on the stack a non-referenceable type with the given ID starts
+    /// containing itself, if it does. Going through a referenceable type on
+    /// the way is fine - that one becomes a ref and breaks the cycle.
+    fn cycle_start(&self, id: &TypeId) -> Option<usize> {
+        let start = self.stack.iter().rposition(|(other, _)| other == id)?;
+        self.stack[start + 1..]
+            .iter()
+            .all(|(other, _)| !self.definitions.contains_key(other))
+            .then_some(start)
+    }
+
+    /// The long names of the types on the stack from `start` on, followed by
+    /// `ty`.
+    fn chain(&self, start: usize, ty: &dyn JsonTypedefDyn) -> Vec<String> {
+        self.stack[start..]
+            .iter()
+            .map(|(_, names)| names)
+            .chain([&ty.dyn_names()])
+            .map(Names::to_string)
+            .collect()
+    }
+
+    /// Record an error to be reported once generation is done. Only the first
+    /// one is kept.
+    fn fail(&mut self, error: GenError) {
+        self.error.get_or_insert(error);
+    }
+
     fn clean_up_defs(&mut self) {
         let to_remove: Vec<_> = self
             .definitions
@@ -209,6 +623,57 @@ impl Generator {
     }
 }
 
+impl GenError {
+    /// An [`InvalidName`](Self::InvalidName) error, for custom naming
+    /// strategies to return.
+    pub fn invalid_name(names: &Names, reason: impl Into<String>) -> Self {
+        Self::InvalidName {
+            type_name: names.to_string(),
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Find definitions that are nothing but a ref leading back to themselves,
+/// possibly through other such definitions. Those don't describe any data.
+fn check_ref_cycles(defs: &HashMap<String, (Names, Schema)>) -> Result<(), GenError> {
+    let target = |key: &str| match defs.get(key) {
+        Some((
+            _,
+            Schema {
+                ty: SchemaType::Ref { r#ref },
+                ..
+            },
+        )) => Some(r#ref.as_str()),
+        _ => None,
+    };
+
+    let mut keys: Vec<_> = defs.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+
+    for start in keys {
+        let mut chain = vec![start];
+        while let Some(next) = target(chain[chain.len() - 1]) {
+            if next == start {
+                chain.push(next);
+                return Err(GenError::UnrepresentableCycle {
+                    chain: chain
+                        .into_iter()
+                        .map(|key| defs[key].0.to_string())
+                        .collect(),
+                });
+            }
+            if chain.contains(&next) {
+                // a cycle that doesn't include `start`. we'll get to it
+                break;
+            }
+            chain.push(next);
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 enum Inlining {
     Always,
@@ -217,11 +682,89 @@ enum Inlining {
     Never,
 }
 
+/// Which direction of (de)serialization schemas describe. See
+/// [`GeneratorBuilder::mode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaMode {
+    /// What the type produces when serialized.
+    Serialize,
+    /// What the type accepts when deserialized. This is the default.
+    #[default]
+    Deserialize,
+}
+
+/// How the [`Generator`] represents byte sequences like `Vec<u8>`, `[u8]`
+/// and `[u8; N]`. See [`GeneratorBuilder::byte_sequences`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ByteSequences {
+    /// An array of `uint8` elements. This is how `serde_json` serializes
+    /// them by default.
+    #[default]
+    Elements,
+    /// A base64-encoded string, with a `contentEncoding` metadata entry of
+    /// `"base64"`.
+    Base64,
+}
+
+/// How the [`Generator`] represents 64-bit integers (`u64`, `i64`, `usize`,
+/// `isize`), which _Typedef_ has no types for. See
+/// [`GeneratorBuilder::integers_64`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Integers64 {
+    /// Fail generation with [`GenError::UnsupportedInteger`].
+    #[default]
+    Error,
+    /// An `int32` or `uint32` schema, with a `format` metadata entry of
+    /// `"int64"` or `"uint64"`. Validators will reject values that don't fit
+    /// in 32 bits, so this is only right if they never occur in practice.
+    Int32,
+    /// A string schema, with a `format` metadata entry of `"int64"` or
+    /// `"uint64"`. This is for types serialized as strings, e.g. with
+    /// `serde_with::DisplayFromStr`, which keeps their precision intact no
+    /// matter what parses the JSON.
+    String,
+}
+
+/// How the [`Generator`] represents 128-bit integers (`u128`, `i128`). See
+/// [`GeneratorBuilder::integers_128`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Integers128 {
+    /// Fail generation with [`GenError::UnsupportedInteger`].
+    #[default]
+    Error,
+    /// A string schema, with a `format` metadata entry of `"int128"` or
+    /// `"uint128"`, for types serialized as strings.
+    String,
+    /// A `float64` schema, with a `format` metadata entry of `"int128"` or
+    /// `"uint128"`. Most JSON parsers will lose precision for large values.
+    Float64,
+}
+
 /// Builder for [`Generator`]. For example usage, refer to [`Generator`].
 #[derive(Default, Debug)]
 pub struct GeneratorBuilder {
     inlining: Inlining,
     naming_strategy: Option<NamingStrategy>,
+    property_case: Option<PropertyCase>,
+    max_depth: Option<usize>,
+    byte_sequences: ByteSequences,
+    integers_64: Integers64,
+    integers_128: Integers128,
+    omit_char_length: bool,
+    deny_non_string_keys: bool,
+    enum_keys_as_properties: bool,
+    source_locations: bool,
+    descriptions: bool,
+    mode: SchemaMode,
+    version: Option<String>,
+    metadata_namespace: Option<Cow<'static, str>>,
 }
 
 impl GeneratorBuilder {
@@ -286,25 +829,418 @@ impl GeneratorBuilder {
         self
     }
 
-    /// Use a custom naming strategy.
-    pub fn naming_custom(&mut self, f: impl Fn(&Names) -> String + 'static) -> &mut Self {
+    /// Use a custom naming strategy. The function can either return a `String`
+    /// or a `Result<String, GenError>`. See [`IntoName`].
+    pub fn naming_custom<N: IntoName>(&mut self, f: impl Fn(&Names) -> N + 'static) -> &mut Self {
         self.naming_strategy = Some(NamingStrategy::custom(f));
         self
     }
 
+    /// Use a custom naming strategy that gets more information about the type
+    /// being named than just its [`Names`]. See [`NamingContext`] and
+    /// [`IntoName`].
+    ///
+    /// ```
+    /// use jtd_derive::{JsonTypedef, Generator};
+    ///
+    /// #[derive(JsonTypedef)]
+    /// struct Foo {
+    ///     x: u32,
+    /// }
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .top_level_ref()
+    ///     .naming_custom_with_context(|ctx| {
+    ///         if ctx.is_root {
+    ///             "Root".to_string()
+    ///         } else {
+    ///             ctx.names.to_string()
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .into_root_schema::<Foo>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(root_schema.definitions.keys().collect::<Vec<_>>(), ["Root"]);
+    /// ```
+    pub fn naming_custom_with_context<N: IntoName>(
+        &mut self,
+        f: impl Fn(&NamingContext) -> N + 'static,
+    ) -> &mut Self {
+        self.naming_strategy = Some(NamingStrategy::custom_with_context(f));
+        self
+    }
+
+    /// Force a naming convention on all property keys (and discriminator tags)
+    /// in the generated schema, regardless of how the types are annotated.
+    ///
+    /// This is meant as a safety net for codebases where some types might be
+    /// missing a `rename_all`. Note it only affects the schema. If `serde`
+    /// isn't configured to match, the schema won't describe the actual
+    /// serialized data.
+    ///
+    /// Generation fails if two properties of one object end up with the same
+    /// key.
+    ///
+    /// ```
+    /// use jtd_derive::{JsonTypedef, Generator, PropertyCase};
+    ///
+    /// #[derive(JsonTypedef)]
+    /// struct Foo {
+    ///     some_field: u32,
+    /// }
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .property_case(PropertyCase::Camel)
+    ///     .build()
+    ///     .into_root_schema::<Foo>()
+    ///     .unwrap();
+    /// let json_schema = serde_json::to_value(&root_schema).unwrap();
+    ///
+    /// assert_eq!(json_schema, serde_json::json!{ {
+    ///     "properties": {
+    ///         "someField": { "type": "uint32" }
+    ///     },
+    ///     "additionalProperties": true,
+    /// } });
+    /// ```
+    pub fn property_case(&mut self, case: PropertyCase) -> &mut Self {
+        self.property_case = Some(case);
+        self
+    }
+
+    /// Limit how deeply types can be nested during schema generation. If the
+    /// limit is exceeded, [`Generator::into_root_schema`] returns
+    /// [`GenError::DepthLimitExceeded`] rather than risking a stack overflow.
+    ///
+    /// The default is [`Generator::DEFAULT_MAX_DEPTH`]. Recursive types don't
+    /// count against the limit more than once, since they're expressed using
+    /// refs.
+    pub fn max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Choose how byte sequences (`Vec<u8>`, `[u8]`, `&[u8]`, `[u8; N]`) are
+    /// represented. Useful for codebases that consistently serialize them
+    /// with something like `serde_bytes` or base64.
+    ///
+    /// ```
+    /// use jtd_derive::{ByteSequences, Generator, JsonTypedef};
+    ///
+    /// #[derive(JsonTypedef)]
+    /// struct Foo {
+    ///     payload: Vec<u8>,
+    /// }
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .byte_sequences(ByteSequences::Base64)
+    ///     .build()
+    ///     .into_root_schema::<Foo>()
+    ///     .unwrap();
+    /// let json_schema = serde_json::to_value(&root_schema).unwrap();
+    ///
+    /// assert_eq!(json_schema, serde_json::json!{ {
+    ///     "properties": {
+    ///         "payload": {
+    ///             "type": "string",
+    ///             "metadata": { "contentEncoding": "base64" }
+    ///         }
+    ///     },
+    ///     "additionalProperties": true,
+    /// } });
+    /// ```
+    pub fn byte_sequences(&mut self, repr: ByteSequences) -> &mut Self {
+        self.byte_sequences = repr;
+        self
+    }
+
+    /// Choose how 64-bit integers (`u64`, `i64`, `usize`, `isize`) are
+    /// represented. _Typedef_ has no 64-bit integer types, and many JSON
+    /// parsers lose precision beyond 53 bits, so by default generating a
+    /// schema for them fails with [`GenError::UnsupportedInteger`].
+    ///
+    /// ```
+    /// use jtd_derive::{Generator, Integers64, JsonTypedef};
+    ///
+    /// #[derive(JsonTypedef)]
+    /// struct Foo {
+    ///     id: u64,
+    /// }
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .integers_64(Integers64::String)
+    ///     .build()
+    ///     .into_root_schema::<Foo>()
+    ///     .unwrap();
+    /// let json_schema = serde_json::to_value(&root_schema).unwrap();
+    ///
+    /// assert_eq!(json_schema, serde_json::json!{ {
+    ///     "properties": {
+    ///         "id": {
+    ///             "type": "string",
+    ///             "metadata": { "format": "uint64" }
+    ///         }
+    ///     },
+    ///     "additionalProperties": true,
+    /// } });
+    /// ```
+    pub fn integers_64(&mut self, repr: Integers64) -> &mut Self {
+        self.integers_64 = repr;
+        self
+    }
+
+    /// Choose how 128-bit integers (`u128`, `i128`) are represented. Like
+    /// with [`integers_64`](Self::integers_64), generating a schema for them
+    /// fails with [`GenError::UnsupportedInteger`] by default.
+    pub fn integers_128(&mut self, repr: Integers128) -> &mut Self {
+        self.integers_128 = repr;
+        self
+    }
+
+    /// By default, `char` is a string schema with `minLength` and `maxLength`
+    /// metadata entries of `1`, so validators and documentation tools know
+    /// it's a single character. This makes it a bare string schema instead.
+    pub fn omit_char_length(&mut self) -> &mut Self {
+        self.omit_char_length = true;
+        self
+    }
+
+    /// Report [`GenError::NonStringKey`] for maps whose keys aren't strings or
+    /// string enums, e.g. integers. `serde_json` accepts those, but their
+    /// format is lost on the way to _Typedef_.
+    pub fn deny_non_string_keys(&mut self) -> &mut Self {
+        self.deny_non_string_keys = true;
+        self
+    }
+
+    /// Represent maps keyed by unit enums as properties schemas with one
+    /// optional property per variant, rather than values schemas that accept
+    /// any key.
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use jtd_derive::{Generator, JsonTypedef};
+    ///
+    /// #[derive(JsonTypedef, PartialEq, Eq, PartialOrd, Ord)]
+    /// enum Color {
+    ///     Red,
+    ///     Green,
+    /// }
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .enum_keys_as_properties()
+    ///     .build()
+    ///     .into_root_schema::<BTreeMap<Color, u32>>()
+    ///     .unwrap();
+    /// let json_schema = serde_json::to_value(&root_schema).unwrap();
+    ///
+    /// assert_eq!(json_schema, serde_json::json!{ {
+    ///     "optionalProperties": {
+    ///         "Red": { "type": "uint32" },
+    ///         "Green": { "type": "uint32" },
+    ///     },
+    /// } });
+    /// ```
+    pub fn enum_keys_as_properties(&mut self) -> &mut Self {
+        self.enum_keys_as_properties = true;
+        self
+    }
+
+    /// Record where each type with a definition is defined in a
+    /// `sourceLocation` metadata entry, like `"src/lib.rs:12"`. Handy for
+    /// tracing a schema back to code during development.
+    ///
+    /// Derived types only provide their location in debug builds (see
+    /// [`JsonTypedef::source_location`]), so this does nothing in release
+    /// builds.
+    pub fn source_locations(&mut self) -> &mut Self {
+        self.source_locations = true;
+        self
+    }
+
+    /// Copy the doc comments of derived types and their fields into
+    /// `description` metadata entries.
+    ///
+    /// ```
+    /// use jtd_derive::{Generator, JsonTypedef};
+    ///
+    /// /// A point on a plane.
+    /// #[derive(JsonTypedef)]
+    /// struct Point {
+    ///     x: i32,
+    ///     y: i32,
+    /// }
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .descriptions()
+    ///     .build()
+    ///     .into_root_schema::<Point>()
+    ///     .unwrap();
+    /// let json_schema = serde_json::to_value(&root_schema).unwrap();
+    ///
+    /// assert_eq!(json_schema, serde_json::json!{ {
+    ///     "metadata": {
+    ///         "description": "A point on a plane."
+    ///     },
+    ///     "properties": {
+    ///         "x": { "type": "int32" },
+    ///         "y": { "type": "int32" }
+    ///     },
+    ///     "additionalProperties": true,
+    /// } });
+    /// ```
+    pub fn descriptions(&mut self) -> &mut Self {
+        self.descriptions = true;
+        self
+    }
+
+    /// Choose whether schemas describe what types serialize to or what they
+    /// can be deserialized from. These differ for types using serde
+    /// attributes like `skip_serializing`, `skip_deserializing`, `default`,
+    /// `skip_serializing_if` or direction-specific `rename`/`rename_all`.
+    /// The default is [`SchemaMode::Deserialize`].
+    ///
+    /// ```
+    /// use jtd_derive::{Generator, JsonTypedef, SchemaMode};
+    ///
+    /// #[derive(JsonTypedef, serde::Deserialize)]
+    /// struct Foo {
+    ///     #[serde(default)]
+    ///     x: u32,
+    /// }
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .mode(SchemaMode::Serialize)
+    ///     .build()
+    ///     .into_root_schema::<Foo>()
+    ///     .unwrap();
+    /// let json_schema = serde_json::to_value(&root_schema).unwrap();
+    ///
+    /// assert_eq!(json_schema, serde_json::json!{ {
+    ///     "properties": {
+    ///         "x": { "type": "uint32" }
+    ///     },
+    ///     "additionalProperties": true,
+    /// } });
+    /// ```
+    pub fn mode(&mut self, mode: SchemaMode) -> &mut Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Generate schemas for a specific API version, dropping fields marked with
+    /// `#[typedef(since = "...")]` or `#[typedef(until = "...")]` that aren't
+    /// available in it. `since` is inclusive, `until` is exclusive.
+    ///
+    /// Versions are dot-separated numbers compared component by component,
+    /// with missing components counting as zero, so `"1.2"` is the same as
+    /// `"1.2.0"`. An invalid version makes [`Generator::into_root_schema`]
+    /// return [`GenError::InvalidVersion`].
+    ///
+    /// ```
+    /// use jtd_derive::{Generator, JsonTypedef};
+    ///
+    /// #[derive(JsonTypedef)]
+    /// struct Foo {
+    ///     #[typedef(until = "2.0")]
+    ///     old: u32,
+    ///     #[typedef(since = "1.5")]
+    ///     new: u32,
+    /// }
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .for_version("1.2")
+    ///     .build()
+    ///     .into_root_schema::<Foo>()
+    ///     .unwrap();
+    /// let json_schema = serde_json::to_value(&root_schema).unwrap();
+    ///
+    /// assert_eq!(json_schema, serde_json::json!{ {
+    ///     "properties": {
+    ///         "old": {
+    ///             "type": "uint32",
+    ///             "metadata": { "versions": { "until": "2.0" } }
+    ///         }
+    ///     },
+    ///     "additionalProperties": true,
+    /// } });
+    /// ```
+    pub fn for_version(&mut self, version: impl Into<String>) -> &mut Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Nest the metadata entries this crate emits (constraints, discriminants,
+    /// key schemas, source locations and so on) in an object under the given
+    /// key, keeping the top level of the metadata free for your own
+    /// conventions. Entries from `#[typedef(metadata(...))]` attributes and
+    /// `jtd-fuzz` hints stay where they are.
+    ///
+    /// ```
+    /// use jtd_derive::{Generator, JsonTypedef};
+    ///
+    /// #[derive(JsonTypedef)]
+    /// #[typedef(discriminants, metadata(owner = "billing"))]
+    /// enum Plan {
+    ///     Free = 0,
+    ///     Pro = 1,
+    /// }
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .metadata_namespace("x-jtd-derive")
+    ///     .build()
+    ///     .into_root_schema::<Plan>()
+    ///     .unwrap();
+    /// let json_schema = serde_json::to_value(&root_schema).unwrap();
+    ///
+    /// assert_eq!(json_schema, serde_json::json!{ {
+    ///     "enum": ["Free", "Pro"],
+    ///     "metadata": {
+    ///         "owner": "billing",
+    ///         "x-jtd-derive": {
+    ///             "discriminants": { "Free": 0, "Pro": 1 }
+    ///         }
+    ///     }
+    /// } });
+    /// ```
+    pub fn metadata_namespace(&mut self, key: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.metadata_namespace = Some(key.into());
+        self
+    }
+
     /// Finalize the configuration and get a `Generator`.
     pub fn build(&mut self) -> Generator {
         Generator {
             inlining: self.inlining,
             naming_strategy: self.naming_strategy.take().unwrap_or_default(),
+            property_case: self.property_case,
+            max_depth: self.max_depth.unwrap_or(Generator::DEFAULT_MAX_DEPTH),
+            byte_sequences: self.byte_sequences,
+            integers_64: self.integers_64,
+            integers_128: self.integers_128,
+            char_length: !self.omit_char_length,
+            deny_non_string_keys: self.deny_non_string_keys,
+            enum_keys_as_properties: self.enum_keys_as_properties,
+            source_locations: self.source_locations,
+            descriptions: self.descriptions,
+            mode: self.mode,
+            version: self.version.as_deref().and_then(Version::parse),
+            metadata_namespace: self.metadata_namespace.clone(),
+            error: self
+                .version
+                .as_ref()
+                .filter(|v| Version::parse(v).is_none())
+                .map(|v| GenError::InvalidVersion { version: v.clone() }),
             ..Generator::default()
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 enum DefinitionState {
     Finished(Schema),
+    #[default]
     Processing,
 }
 
@@ -325,12 +1261,6 @@ impl DefinitionState {
     }
 }
 
-impl Default for DefinitionState {
-    fn default() -> Self {
-        Self::Processing
-    }
-}
-
 /// Schema generation errors.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, thiserror::Error)]
 pub enum GenError {
@@ -342,4 +1272,128 @@ pub enum GenError {
         type2: String,
         id: String,
     },
+    /// Two properties of one object ended up with the same key after applying
+    /// the [`PropertyCase`].
+    #[error("properties \"{property1}\" and \"{property2}\" are both renamed to \"{cased}\"")]
+    PropertyCollision {
+        property1: String,
+        property2: String,
+        cased: String,
+    },
+    /// A type contains itself without any object or array in between, e.g.
+    /// a transparent newtype around a `Box` of itself. There's no finite
+    /// schema for that. The chain lists the types involved, starting and
+    /// ending with the same one.
+    #[error("type `{}` contains itself in a way Typedef can't express: {}", chain[0], chain.join(" -> "))]
+    UnrepresentableCycle { chain: Vec<String> },
+    /// A custom naming strategy refused to name a type.
+    #[error("can't name type `{type_name}`: {reason}")]
+    InvalidName { type_name: String, reason: String },
+    /// Types are nested more deeply than the configured limit allows. The chain
+    /// lists the nested types, outermost first.
+    #[error("types are nested more than {limit} levels deep: {}", chain.join(" -> "))]
+    DepthLimitExceeded { limit: usize, chain: Vec<String> },
+    /// A map key type doesn't serialize as a string or string enum. Only
+    /// reported with [`GeneratorBuilder::deny_non_string_keys`].
+    #[error("map key type `{key_type}` doesn't serialize as a string")]
+    NonStringKey { key_type: String },
+    /// The version passed to [`GeneratorBuilder::for_version`] isn't made of
+    /// dot-separated numbers.
+    #[error("invalid version \"{version}\"")]
+    InvalidVersion { version: String },
+    /// A flattened type has neither a properties nor a values schema, so
+    /// there's nothing to merge into the parent.
+    #[error(
+        "type `{type_name}` can't be flattened since it isn't described by a properties or values schema"
+    )]
+    InvalidFlatten { type_name: String },
+    /// An integer type is too wide for _Typedef_, and the generator isn't
+    /// configured to represent it some other way. See
+    /// [`GeneratorBuilder::integers_64`] and
+    /// [`GeneratorBuilder::integers_128`]. The chain lists the types the
+    /// integer was found in, outermost first, followed by the integer type.
+    #[error(
+        "`{type_name}` has no Typedef equivalent (in {}); pick a representation with `GeneratorBuilder::{}`",
+        chain.join(" -> "),
+        if *bits == 64 { "integers_64" } else { "integers_128" }
+    )]
+    UnsupportedInteger {
+        type_name: String,
+        bits: u32,
+        chain: Vec<String>,
+    },
+}
+
+/// Metadata keys this crate emits on its own. See
+/// [`GeneratorBuilder::metadata_namespace`].
+const EMITTED_METADATA_KEYS: &[&str] = &[
+    // constraints
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "multipleOf",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minItems",
+    "maxItems",
+    "uniqueItems",
+    "minProperties",
+    "maxProperties",
+    "enum",
+    // everything else
+    "allowedValues",
+    "catchAll",
+    "contentEncoding",
+    "deprecated",
+    "deprecatedVariants",
+    "deprecationNote",
+    "discriminants",
+    "format",
+    "keySchema",
+    "sourceLocation",
+    "tupleElements",
+    "uninhabited",
+    "versions",
+];
+
+/// A version made of dot-separated numbers, compared component by component.
+#[derive(Debug, Clone)]
+struct Version(Vec<u64>);
+
+impl Version {
+    fn parse(s: &str) -> Option<Self> {
+        s.split('.')
+            .map(|part| part.parse().ok())
+            .collect::<Option<_>>()
+            .map(Self)
+    }
+
+    fn component(&self, i: usize) -> u64 {
+        self.0.get(i).copied().unwrap_or(0)
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (0..self.0.len().max(other.0.len()))
+            .map(|i| self.component(i).cmp(&other.component(i)))
+            .find(|o| o.is_ne())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    }
 }
@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ByteSequences, Generator, Integers128, Integers64, PropertyCase, SchemaMode};
+
+/// A serializable description of how to configure a [`Generator`], for when
+/// schema generation is driven by a config file (e.g. from a build script)
+/// rather than code. Every field is optional and defaults to the same thing
+/// the corresponding [`GeneratorBuilder`](super::GeneratorBuilder) method
+/// would.
+///
+/// Custom naming strategies can't be expressed in a config file, only the
+/// presets can.
+///
+/// # Example
+///
+/// ```
+/// use jtd_derive::{Generator, GeneratorConfig, JsonTypedef};
+///
+/// #[derive(JsonTypedef)]
+/// struct Foo {
+///     some_field: u32,
+/// }
+///
+/// let config: GeneratorConfig = serde_json::from_str(r#"{
+///     "inlining": "never",
+///     "naming": "short",
+///     "property_case": "camelCase"
+/// }"#).unwrap();
+///
+/// let root_schema = Generator::from_config(&config)
+///     .into_root_schema::<Foo>()
+///     .unwrap();
+/// let json_schema = serde_json::to_value(&root_schema).unwrap();
+///
+/// assert_eq!(json_schema, serde_json::json!{ {
+///     "definitions": {
+///         "Foo": {
+///             "properties": {
+///                 "someField": { "type": "uint32" }
+///             },
+///             "additionalProperties": true,
+///         }
+///     },
+///     "ref": "Foo",
+/// } });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[non_exhaustive]
+pub struct GeneratorConfig {
+    /// See [`GeneratorBuilder::prefer_inline`](super::GeneratorBuilder::prefer_inline)
+    /// and [`GeneratorBuilder::top_level_ref`](super::GeneratorBuilder::top_level_ref).
+    pub inlining: InliningMode,
+    /// See [`GeneratorBuilder::naming_short`](super::GeneratorBuilder::naming_short)
+    /// and [`GeneratorBuilder::naming_long`](super::GeneratorBuilder::naming_long).
+    pub naming: NamingPreset,
+    /// See [`GeneratorBuilder::property_case`](super::GeneratorBuilder::property_case).
+    pub property_case: Option<PropertyCase>,
+    /// See [`GeneratorBuilder::max_depth`](super::GeneratorBuilder::max_depth).
+    pub max_depth: Option<usize>,
+    /// See [`GeneratorBuilder::byte_sequences`](super::GeneratorBuilder::byte_sequences).
+    pub byte_sequences: ByteSequences,
+    /// See [`GeneratorBuilder::integers_64`](super::GeneratorBuilder::integers_64).
+    pub integers_64: Integers64,
+    /// See [`GeneratorBuilder::integers_128`](super::GeneratorBuilder::integers_128).
+    pub integers_128: Integers128,
+    /// See [`GeneratorBuilder::omit_char_length`](super::GeneratorBuilder::omit_char_length).
+    pub omit_char_length: bool,
+    /// See [`GeneratorBuilder::deny_non_string_keys`](super::GeneratorBuilder::deny_non_string_keys).
+    pub deny_non_string_keys: bool,
+    /// See [`GeneratorBuilder::enum_keys_as_properties`](super::GeneratorBuilder::enum_keys_as_properties).
+    pub enum_keys_as_properties: bool,
+    /// See [`GeneratorBuilder::source_locations`](super::GeneratorBuilder::source_locations).
+    pub source_locations: bool,
+    /// See [`GeneratorBuilder::descriptions`](super::GeneratorBuilder::descriptions).
+    pub descriptions: bool,
+    /// See [`GeneratorBuilder::mode`](super::GeneratorBuilder::mode).
+    pub mode: SchemaMode,
+    /// See [`GeneratorBuilder::for_version`](super::GeneratorBuilder::for_version).
+    pub for_version: Option<String>,
+    /// See [`GeneratorBuilder::metadata_namespace`](super::GeneratorBuilder::metadata_namespace).
+    pub metadata_namespace: Option<String>,
+}
+
+/// When types are provided through definitions and refs. See
+/// [`GeneratorConfig::inlining`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum InliningMode {
+    /// Inline the top-level type, use refs for everything else.
+    #[default]
+    Normal,
+    /// Inline everything except recursive types.
+    Always,
+    /// Use refs for everything, including the top-level type.
+    Never,
+}
+
+/// The built-in naming strategies. See [`GeneratorConfig::naming`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum NamingPreset {
+    /// Type names with their paths.
+    #[default]
+    Long,
+    /// Type names without their paths.
+    Short,
+}
+
+impl Generator {
+    /// Create a `Generator` configured according to a [`GeneratorConfig`].
+    pub fn from_config(config: &GeneratorConfig) -> Self {
+        let mut builder = Self::builder();
+
+        match config.inlining {
+            InliningMode::Normal => {}
+            InliningMode::Always => {
+                builder.prefer_inline();
+            }
+            InliningMode::Never => {
+                builder.top_level_ref();
+            }
+        }
+        match config.naming {
+            NamingPreset::Long => builder.naming_long(),
+            NamingPreset::Short => builder.naming_short(),
+        };
+        if let Some(case) = config.property_case {
+            builder.property_case(case);
+        }
+        if let Some(max_depth) = config.max_depth {
+            builder.max_depth(max_depth);
+        }
+        builder.byte_sequences(config.byte_sequences);
+        builder.integers_64(config.integers_64);
+        builder.integers_128(config.integers_128);
+        if config.omit_char_length {
+            builder.omit_char_length();
+        }
+        if config.deny_non_string_keys {
+            builder.deny_non_string_keys();
+        }
+        if config.enum_keys_as_properties {
+            builder.enum_keys_as_properties();
+        }
+        if config.source_locations {
+            builder.source_locations();
+        }
+        if config.descriptions {
+            builder.descriptions();
+        }
+        builder.mode(config.mode);
+        if let Some(version) = &config.for_version {
+            builder.for_version(version.clone());
+        }
+        if let Some(namespace) = &config.metadata_namespace {
+            builder.metadata_namespace(namespace.clone());
+        }
+
+        builder.build()
+    }
+}
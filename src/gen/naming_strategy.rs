@@ -1,53 +1,80 @@
+use super::GenError;
 use crate::JsonTypedef as _;
 use crate::Names;
 
 /// The naming strategy. The strategy decides how types are named in definitions/refs
 /// in the _Typedef_ schema.
-pub struct NamingStrategy(Box<dyn Fn(&Names) -> String>);
+///
+/// It can also be used to render [`Names`] on its own, e.g. for error messages.
+/// See [`Names::render`].
+pub struct NamingStrategy(Box<StrategyFn>);
 
-impl NamingStrategy {
-    pub fn long() -> Self {
-        fn strategy(names: &Names) -> String {
-            let params = names
-                .type_params
-                .iter()
-                .map(strategy)
-                .chain(names.const_params.clone())
-                .reduce(|l, r| format!("{}, {}", l, r));
+type StrategyFn = dyn Fn(&NamingContext) -> Result<String, GenError>;
 
-            match params {
-                Some(params) => format!("{}<{}>", names.long, params),
-                None => names.long.to_string(),
-            }
+/// What a [`NamingStrategy`] gets to know about the type it's naming.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct NamingContext<'a> {
+    /// The names of the type, including the names of its generic arguments.
+    pub names: &'a Names,
+    /// Whether this is the type the whole schema is generated for. The root
+    /// type only needs a name if it's recursive or the generator is set to
+    /// [`top_level_ref`](super::GeneratorBuilder::top_level_ref).
+    pub is_root: bool,
+}
+
+impl<'a> NamingContext<'a> {
+    /// A context for a type that isn't the root.
+    pub fn new(names: &'a Names) -> Self {
+        Self {
+            names,
+            is_root: false,
         }
+    }
+}
 
-        Self(Box::new(strategy))
+impl NamingStrategy {
+    /// The full path of the type, with generic arguments in angle brackets.
+    /// Same as the [`Display`](std::fmt::Display) impl of [`Names`].
+    pub fn long() -> Self {
+        Self(Box::new(|ctx| Ok(ctx.names.to_string())))
     }
 
+    /// The ident of the type, with generic arguments in angle brackets.
+    /// Same as the alternate [`Display`](std::fmt::Display) form of [`Names`].
     pub fn short() -> Self {
-        fn strategy(names: &Names) -> String {
-            let params = names
-                .type_params
-                .iter()
-                .map(strategy)
-                .chain(names.const_params.clone())
-                .reduce(|l, r| format!("{}, {}", l, r));
+        Self(Box::new(|ctx| Ok(format!("{:#}", ctx.names))))
+    }
 
-            match params {
-                Some(params) => format!("{}<{}>", names.short, params),
-                None => names.short.to_string(),
-            }
-        }
+    /// A custom strategy. See [`IntoName`] for what the function can return.
+    pub fn custom<F, N>(fun: F) -> Self
+    where
+        F: Fn(&Names) -> N + 'static,
+        N: IntoName,
+    {
+        Self(Box::new(move |ctx| fun(ctx.names).into_name()))
+    }
 
-        Self(Box::new(strategy))
+    /// A custom strategy that gets the whole [`NamingContext`]. See [`IntoName`]
+    /// for what the function can return.
+    pub fn custom_with_context<F, N>(fun: F) -> Self
+    where
+        F: Fn(&NamingContext) -> N + 'static,
+        N: IntoName,
+    {
+        Self(Box::new(move |ctx| fun(ctx).into_name()))
     }
 
-    pub fn custom<F: Fn(&Names) -> String + 'static>(fun: F) -> Self {
-        Self(Box::new(fun))
+    /// Name a type according to this strategy. This can only fail for
+    /// custom strategies.
+    pub fn apply(&self, names: &Names) -> Result<String, GenError> {
+        self.apply_with_context(&NamingContext::new(names))
     }
 
-    pub fn fun(&self) -> &dyn Fn(&Names) -> String {
-        &self.0
+    /// Name a type according to this strategy. This can only fail for
+    /// custom strategies.
+    pub fn apply_with_context(&self, ctx: &NamingContext) -> Result<String, GenError> {
+        (self.0)(ctx)
     }
 }
 
@@ -62,15 +89,62 @@ impl std::fmt::Debug for NamingStrategy {
         let example = Names {
             short: "Foo",
             long: "my_crate::Foo",
+            path_segments: vec!["my_crate", "Foo"],
             nullable: false,
             type_params: vec![u32::names()],
             const_params: vec!["5".to_string()],
         };
-        let result = self.fun()(&example);
+        match self.apply(&example) {
+            Ok(result) => f.write_fmt(format_args!(
+                "NamingStrategy(Foo<u32, 5> -> \"{}\")",
+                result
+            )),
+            Err(e) => f.write_fmt(format_args!("NamingStrategy(Foo<u32, 5> -> error: {})", e)),
+        }
+    }
+}
+
+/// What custom naming strategies can return: either a plain `String`, or a
+/// `Result` so that the strategy can reject types it can't name sensibly.
+///
+/// # Example
+///
+/// ```
+/// use jtd_derive::{GenError, Generator, JsonTypedef};
+///
+/// #[derive(JsonTypedef)]
+/// struct Foo<T> {
+///     x: T,
+/// }
+///
+/// let err = Generator::builder()
+///     .top_level_ref()
+///     .naming_custom(|names| match names.type_params.is_empty() {
+///         true => Ok(names.short.to_string()),
+///         false => Err(GenError::invalid_name(names, "generics aren't supported")),
+///     })
+///     .build()
+///     .into_root_schema::<Foo<u32>>()
+///     .unwrap_err();
+///
+/// assert_eq!(
+///     err.to_string(),
+///     "can't name type `rust_out::Foo<uint32>`: generics aren't supported"
+/// );
+/// ```
+pub trait IntoName {
+    /// Convert into the result of naming a type.
+    fn into_name(self) -> Result<String, GenError>;
+}
+
+impl IntoName for String {
+    fn into_name(self) -> Result<String, GenError> {
+        Ok(self)
+    }
+}
 
-        f.write_fmt(format_args!(
-            "NamingStrategy(Foo<u32, 5> -> \"{}\")",
-            result
-        ))
+impl IntoName for Result<String, GenError> {
+    fn into_name(self) -> Result<String, GenError> {
+        self
     }
 }
@@ -0,0 +1,176 @@
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::schema::{Schema, SchemaType};
+
+use super::GenError;
+
+/// A naming convention the [`Generator`](super::Generator) can force on all
+/// property keys. See
+/// [`GeneratorBuilder::property_case`](super::GeneratorBuilder::property_case).
+///
+/// Keys are split into words at underscores, dashes and lowercase-to-uppercase
+/// transitions, so this works regardless of the convention the keys were
+/// originally written in.
+///
+/// In config files, cases are spelled like serde's `rename_all` values, e.g.
+/// `"camelCase"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum PropertyCase {
+    /// `lowercase`
+    #[serde(rename = "lowercase")]
+    Lower,
+    /// `UPPERCASE`
+    #[serde(rename = "UPPERCASE")]
+    Upper,
+    /// `camelCase`
+    #[serde(rename = "camelCase")]
+    Camel,
+    /// `PascalCase`
+    #[serde(rename = "PascalCase")]
+    Pascal,
+    /// `snake_case`
+    #[serde(rename = "snake_case")]
+    Snake,
+    /// `SCREAMING_SNAKE_CASE`
+    #[serde(rename = "SCREAMING_SNAKE_CASE")]
+    ScreamingSnake,
+    /// `kebab-case`
+    #[serde(rename = "kebab-case")]
+    Kebab,
+    /// `SCREAMING-KEBAB-CASE`
+    #[serde(rename = "SCREAMING-KEBAB-CASE")]
+    ScreamingKebab,
+}
+
+impl PropertyCase {
+    /// Apply the convention to a single key.
+    ///
+    /// ```
+    /// use jtd_derive::PropertyCase;
+    ///
+    /// assert_eq!(PropertyCase::Camel.apply("foo_bar"), "fooBar");
+    /// assert_eq!(PropertyCase::Snake.apply("fooBar"), "foo_bar");
+    /// assert_eq!(PropertyCase::ScreamingKebab.apply("FooBar"), "FOO-BAR");
+    /// ```
+    pub fn apply(self, key: &str) -> String {
+        let words = words(key);
+        let words = words.iter().map(|w| w.to_lowercase());
+
+        match self {
+            Self::Lower => words.collect(),
+            Self::Upper => words.map(|w| w.to_uppercase()).collect(),
+            Self::Camel => words
+                .enumerate()
+                .map(|(i, w)| if i == 0 { w } else { capitalize(&w) })
+                .collect(),
+            Self::Pascal => words.map(|w| capitalize(&w)).collect(),
+            Self::Snake => words.collect::<Vec<_>>().join("_"),
+            Self::ScreamingSnake => words.collect::<Vec<_>>().join("_").to_uppercase(),
+            Self::Kebab => words.collect::<Vec<_>>().join("-"),
+            Self::ScreamingKebab => words.collect::<Vec<_>>().join("-").to_uppercase(),
+        }
+    }
+
+    /// Rename the property keys of the schema and all schemas nested in it.
+    pub(super) fn apply_to_schema(self, schema: &mut Schema) -> Result<(), GenError> {
+        match &mut schema.ty {
+            SchemaType::Empty | SchemaType::Type { .. } | SchemaType::Enum { .. } => {}
+            SchemaType::Ref { .. } => {}
+            SchemaType::Elements { elements: inner } | SchemaType::Values { values: inner } => {
+                self.apply_to_schema(inner)?;
+            }
+            SchemaType::Properties {
+                properties,
+                optional_properties,
+                ..
+            } => {
+                let mut seen = BTreeMap::new();
+                *properties = self.apply_to_properties(std::mem::take(properties), &mut seen)?;
+                *optional_properties =
+                    self.apply_to_properties(std::mem::take(optional_properties), &mut seen)?;
+            }
+            SchemaType::Discriminator {
+                discriminator,
+                mapping,
+            } => {
+                *discriminator = intern(self.apply(discriminator));
+                for variant in mapping.values_mut() {
+                    self.apply_to_schema(variant)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn apply_to_properties(
+        self,
+        properties: BTreeMap<&'static str, Schema>,
+        seen: &mut BTreeMap<&'static str, &'static str>,
+    ) -> Result<BTreeMap<&'static str, Schema>, GenError> {
+        properties
+            .into_iter()
+            .map(|(key, mut schema)| {
+                let cased = intern(self.apply(key));
+                if let Some(other) = seen.insert(cased, key) {
+                    return Err(GenError::PropertyCollision {
+                        property1: other.to_string(),
+                        property2: key.to_string(),
+                        cased: cased.to_string(),
+                    });
+                }
+                self.apply_to_schema(&mut schema)?;
+                Ok((cased, schema))
+            })
+            .collect()
+    }
+}
+
+fn words(key: &str) -> Vec<&str> {
+    let mut words = vec![];
+    let mut start = 0;
+    let mut prev: Option<char> = None;
+
+    for (i, c) in key.char_indices() {
+        if c == '_' || c == '-' {
+            words.push(&key[start..i]);
+            start = i + 1;
+        } else if c.is_uppercase() && prev.is_some_and(|p| p.is_lowercase() || p.is_numeric()) {
+            words.push(&key[start..i]);
+            start = i;
+        }
+        prev = Some(c);
+    }
+    words.push(&key[start..]);
+
+    words.retain(|w| !w.is_empty());
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Property keys are `&'static str`s, so renamed ones have to be leaked.
+/// Interning them means each distinct key is only ever leaked once.
+fn intern(key: String) -> &'static str {
+    static KEYS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut keys = KEYS.get_or_init(Default::default).lock().unwrap();
+    match keys.get(key.as_str()) {
+        Some(key) => key,
+        None => {
+            let key = Box::leak(key.into_boxed_str());
+            keys.insert(key);
+            key
+        }
+    }
+}
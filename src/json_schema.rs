@@ -0,0 +1,206 @@
+//! Conversion of [_JSON Typedef_](https://jsontypedef.com/) schemas into
+//! [JSON Schema](https://json-schema.org/) (draft 7).
+//!
+//! _Typedef_ is deliberately less expressive than JSON Schema, so every
+//! _Typedef_ schema has a JSON Schema equivalent. The other way around isn't
+//! true, which is why there's no conversion in the opposite direction.
+//!
+//! Metadata entries are copied into the resulting JSON Schema objects as-is.
+//! This means things like a `description` key in metadata become JSON Schema
+//! annotations. Keywords produced by the conversion itself always take
+//! precedence over metadata entries. The exception is `tupleElements`, which
+//! becomes the `items` of a tuple.
+
+use serde_json::{json, Map, Value};
+
+use crate::schema::RootSchema;
+
+/// The JSON Schema dialect produced by this module.
+pub const DIALECT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// Convert a _Typedef_ schema in its JSON form into a JSON Schema document.
+///
+/// The input is expected to be a valid _Typedef_ root schema. Anything that
+/// doesn't look like one of the _Typedef_ forms is treated as the empty form.
+pub fn from_jtd(schema: &Value) -> Value {
+    let mut root = convert(schema);
+
+    if let Value::Object(root) = &mut root {
+        root.insert("$schema".to_string(), json!(DIALECT));
+
+        if let Some(Value::Object(defs)) = schema.get("definitions") {
+            let defs: Map<_, _> = defs.iter().map(|(k, v)| (k.clone(), convert(v))).collect();
+            root.insert("definitions".to_string(), Value::Object(defs));
+        }
+    }
+
+    root
+}
+
+impl RootSchema {
+    /// Convert this schema into a JSON Schema document. See the
+    /// [`json_schema`](crate::json_schema) module for details.
+    pub fn to_json_schema(&self) -> Value {
+        from_jtd(&serde_json::to_value(self).expect("schemas are always serializable"))
+    }
+
+    /// Convert this schema into a [`schemars`] root schema, going through
+    /// [`to_json_schema`](Self::to_json_schema). Only available with the
+    /// `schemars` feature.
+    ///
+    /// This is meant for projects migrating between the two, so that both
+    /// formats can be served from one set of derives.
+    #[cfg(feature = "schemars")]
+    pub fn to_schemars(&self) -> schemars::schema::RootSchema {
+        serde_json::from_value(self.to_json_schema())
+            .expect("converted schemas are valid JSON Schema documents")
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl From<RootSchema> for schemars::schema::RootSchema {
+    fn from(schema: RootSchema) -> Self {
+        schema.to_schemars()
+    }
+}
+
+fn convert(schema: &Value) -> Value {
+    let mut out = Map::new();
+
+    if let Some(Value::Object(metadata)) = schema.get("metadata") {
+        out.extend(
+            metadata
+                .iter()
+                .filter(|(key, _)| *key != "tupleElements")
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+    }
+
+    let Value::Object(mut form) = convert_form(schema) else {
+        unreachable!("forms are always converted into objects")
+    };
+    let nullable = schema.get("nullable").and_then(Value::as_bool) == Some(true);
+    // a discriminator's variants are objects themselves, so a `null` type on
+    // the outer schema alone would still be rejected by its `oneOf`
+    let discriminator = schema.get("discriminator").is_some();
+
+    if !nullable || form.is_empty() {
+        out.extend(form);
+    } else if let Some(ty @ Value::String(_)) = form.get_mut("type").filter(|_| !discriminator) {
+        *ty = json!([ty.take(), "null"]);
+        if let Some(Value::Array(values)) = form.get_mut("enum") {
+            values.push(Value::Null);
+        }
+        out.extend(form);
+    } else {
+        out.insert(
+            "anyOf".to_string(),
+            json!([{ "type": "null" }, Value::Object(form)]),
+        );
+    }
+
+    Value::Object(out)
+}
+
+fn convert_form(schema: &Value) -> Value {
+    if let Some(Value::String(ty)) = schema.get("type") {
+        convert_type(ty)
+    } else if let Some(values) = schema.get("enum") {
+        json!({ "type": "string", "enum": values })
+    } else if let Some(Value::Array(tuple)) = schema.pointer("/metadata/tupleElements") {
+        json!({ "type": "array", "items": tuple.iter().map(convert).collect::<Vec<_>>() })
+    } else if let Some(elements) = schema.get("elements") {
+        json!({ "type": "array", "items": convert(elements) })
+    } else if let Some(values) = schema.get("values") {
+        json!({ "type": "object", "additionalProperties": convert(values) })
+    } else if let Some(Value::String(tag)) = schema.get("discriminator") {
+        let variants = schema
+            .get("mapping")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .map(|(value, variant)| {
+                let mut variant = convert_properties(variant);
+                variant["properties"][tag] = json!({ "const": value });
+                variant["required"]
+                    .as_array_mut()
+                    .expect("required is always an array")
+                    .insert(0, json!(tag));
+                variant
+            })
+            .collect::<Vec<_>>();
+
+        json!({ "type": "object", "oneOf": variants })
+    } else if schema.get("properties").is_some() || schema.get("optionalProperties").is_some() {
+        convert_properties(schema)
+    } else if let Some(Value::String(r#ref)) = schema.get("ref") {
+        json!({ "$ref": format!("#/definitions/{}", escape_ref(r#ref)) })
+    } else {
+        json!({})
+    }
+}
+
+fn convert_type(ty: &str) -> Value {
+    let int = |min: i64, max: i64| json!({ "type": "integer", "minimum": min, "maximum": max });
+
+    match ty {
+        "boolean" => json!({ "type": "boolean" }),
+        "string" => json!({ "type": "string" }),
+        "timestamp" => json!({ "type": "string", "format": "date-time" }),
+        "float32" | "float64" => json!({ "type": "number" }),
+        "int8" => int(i8::MIN.into(), i8::MAX.into()),
+        "uint8" => int(u8::MIN.into(), u8::MAX.into()),
+        "int16" => int(i16::MIN.into(), i16::MAX.into()),
+        "uint16" => int(u16::MIN.into(), u16::MAX.into()),
+        "int32" => int(i32::MIN.into(), i32::MAX.into()),
+        "uint32" => int(u32::MIN.into(), u32::MAX.into()),
+        _ => json!({}),
+    }
+}
+
+fn convert_properties(schema: &Value) -> Value {
+    let mut properties = Map::new();
+    let mut required = vec![];
+
+    if let Some(Value::Object(props)) = schema.get("properties") {
+        for (name, prop) in props {
+            properties.insert(name.clone(), convert(prop));
+            required.push(json!(name));
+        }
+    }
+    if let Some(Value::Object(props)) = schema.get("optionalProperties") {
+        for (name, prop) in props {
+            properties.insert(name.clone(), convert(prop));
+        }
+    }
+
+    let additional = schema.get("additionalProperties").and_then(Value::as_bool) == Some(true);
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": additional,
+    })
+}
+
+/// Turn a definition name into a JSON pointer segment usable in a URI fragment.
+fn escape_ref(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+
+    for c in name.chars() {
+        match c {
+            '~' => out.push_str("~0"),
+            '/' => out.push_str("~1"),
+            c if c.is_ascii_alphanumeric() || "-._!$&'()*+,;=:@".contains(c) => out.push(c),
+            c => {
+                let mut buf = [0; 4];
+                for b in c.encode_utf8(&mut buf).bytes() {
+                    out.push_str(&format!("%{:02X}", b));
+                }
+            }
+        }
+    }
+
+    out
+}
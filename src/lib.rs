@@ -5,7 +5,8 @@
 //! In order to be able to generate a schema for a type, it must implement the
 //! [`JsonTypedef`] trait. Many types from the Rust standard library already do.
 //! To implement [`JsonTypedef`] for your own types, you'll probably
-//! want to derive it.
+//! want to derive it. Types that can't, like wrappers with custom `serde`
+//! impls, can often use [`impl_json_typedef!`] instead.
 //!
 //! Generating a schema is done by creating a [`Generator`](gen::Generator),
 //! calling [`Generator::into_root_schema`](gen::Generator::into_root_schema),
@@ -31,13 +32,113 @@
 //!     "additionalProperties": true,
 //! } });
 //! ```
+//!
+//! # Optional features
+//!
+//! - `url` - a [`JsonTypedef`] impl for `url::Url`.
+//! - `cli` - builds the `jtd-derive` binary, which can validate JSON instances
+//!   against schema files, diff two schema files, convert them to
+//!   [JSON Schema](json_schema) and pretty-print them.
+//! - `testing` - test helpers, like the `assert_schema_snapshot!` macro and
+//!   round-trip checks of `serde` output against generated schemas.
+//! - `axum`, `actix` - serve the contents of a
+//!   [`SchemaRegistry`](registry::SchemaRegistry) over HTTP.
+//! - `inventory` - derived types register themselves globally, so schemas for
+//!   all of them can be exported without listing them by hand. See
+//!   `registry::all_types`.
+//! - `schemars` - convert schemas into `schemars` root schemas, going through
+//!   the [JSON Schema](json_schema) mapping.
+//! - `serde_valid` - constraints from `serde_valid`'s `#[validate(...)]` field
+//!   attributes (`minimum`, `max_length`, `pattern`, `enumerate`...) are added
+//!   to the field's metadata, under the equivalent JSON Schema keyword names.
+//! - `validator` - the same for `validator`'s `#[validate(...)]` attributes
+//!   (`length`, `range`, `email`, `url`, `regex`).
+//! - `garde` - the same for `garde`'s `#[garde(...)]` attributes (`length`,
+//!   `range`, `email`, `url`, `ipv4`, `ipv6`, `pattern`).
+//! - `serde_with` - fields using common `serde_with` adapters through
+//!   `#[serde_as(as = "...")]` (`DisplayFromStr`, `Base64`, `Hex`,
+//!   `BoolFromInt`, `DurationSeconds` and friends, or containers of those)
+//!   get the schema of what the adapter produces, like with
+//!   `#[typedef(as = "...")]`. Other adapters are a compile error unless the
+//!   schema is given by hand.
+//! - `humantime` - [`JsonTypedef`] impls for `humantime_serde::Serde<Duration>`
+//!   and `humantime_serde::Serde<SystemTime>`. These are strings, with a
+//!   `format` metadata entry of `"humantime-duration"` and `"date-time"`
+//!   respectively. Fields with `#[serde(with = "humantime_serde")]` get the
+//!   same schemas.
+//! - `log`, `tracing` - [`JsonTypedef`] impls for the `Level` and `LevelFilter`
+//!   types of these crates, as enums of the names they're (de)serialized or
+//!   displayed as.
+//! - `indexmap` - [`JsonTypedef`] impls for `IndexMap` and `IndexSet`, the same
+//!   as for `HashMap` and `HashSet`.
+//! - `bytes` - [`JsonTypedef`] impls for `Bytes` and `BytesMut`, the same as
+//!   for `Vec<u8>`. Like that, they're subject to
+//!   [`GeneratorBuilder::byte_sequences`](gen::GeneratorBuilder::byte_sequences).
+//! - `arrayvec` - [`JsonTypedef`] impls for `ArrayVec` and `ArrayString`. These
+//!   are elements and string schemas, with `maxItems` and `maxLength`
+//!   metadata entries taken from the capacity.
+//! - `either` - a [`JsonTypedef`] impl for `Either`. It serializes as
+//!   `{"Left": ...}` or `{"Right": ...}`, which _Typedef_ can only describe
+//!   as an object with two optional properties. `minProperties` and
+//!   `maxProperties` metadata entries say exactly one of them is there.
+//! - `semver` - [`JsonTypedef`] impls for `Version` and `VersionReq`. These
+//!   are strings, with a `format` metadata entry of `"semver"` and
+//!   `"semver-requirement"` respectively.
 
+#[doc(hidden)]
+pub mod __private;
+mod dyn_trait;
 mod gen;
+pub mod json_schema;
 mod names;
+pub mod registry;
 pub mod schema;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod r#trait;
 mod type_id;
 
-pub use gen::{GenError, Generator};
+pub use dyn_trait::{JsonTypedefDyn, TypeOf};
+pub use gen::{
+    ByteSequences, GenError, Generator, GeneratorConfig, InliningMode, Integers128, Integers64,
+    IntoName, NamingContext, NamingPreset, NamingStrategy, PropertyCase, SchemaMode,
+};
 pub use names::Names;
-pub use r#trait::JsonTypedef;
+pub use r#trait::{JsonTypedef, JsonTypedefKey};
+
+/// Derive [`JsonTypedef`] for every struct and enum in a module, without
+/// annotating each of them.
+///
+/// Types that already derive it are left alone, and types marked with
+/// `#[typedef(skip)]` are skipped. Only the module's own items are affected,
+/// not those of nested modules. The module also gets a `register_schemas`
+/// function, which adds the schemas of its non-generic types to a
+/// [`SchemaRegistry`](registry::SchemaRegistry).
+///
+/// # Example
+///
+/// ```
+/// use jtd_derive::registry::SchemaRegistry;
+///
+/// #[jtd_derive::typedef_module]
+/// mod dto {
+///     pub struct User {
+///         pub name: String,
+///     }
+///
+///     #[typedef(rename_all = "lowercase")]
+///     pub enum Role {
+///         Admin,
+///         Member,
+///     }
+///
+///     #[typedef(skip)]
+///     pub struct Internal;
+/// }
+///
+/// let mut registry = SchemaRegistry::new();
+/// dto::register_schemas(&mut registry).unwrap();
+///
+/// assert_eq!(registry.len(), 2);
+/// ```
+pub use jtd_derive_macros::typedef_module;
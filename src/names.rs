@@ -1,8 +1,29 @@
+use std::fmt;
+
+use crate::gen::{GenError, NamingStrategy};
+
 /// How to refer to a given schema. Used mostly for referring to a schema definition
 /// using the ["ref" form](https://jsontypedef.com/docs/jtd-in-5-minutes/#ref-schemas).
 ///
 /// The [`Generator`](crate::gen::Generator) decides how to use this information to
 /// generate an actual identifier.
+///
+/// The [`Display`](fmt::Display) impl renders the long name with any generic
+/// arguments in angle brackets. The alternate form (`{:#}`) uses the short names
+/// instead.
+///
+/// ```
+/// use jtd_derive::JsonTypedef;
+///
+/// #[derive(JsonTypedef)]
+/// struct Foo<T, const N: usize> {
+///     x: [T; N],
+/// }
+///
+/// let names = Foo::<u32, 5>::names();
+/// assert_eq!(names.to_string(), "rust_out::Foo<uint32, 5>");
+/// assert_eq!(format!("{:#}", names), "Foo<uint32, 5>");
+/// ```
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct Names {
     /// The short name. Most of the time this is just the ident of the Rust type.
@@ -10,10 +31,79 @@ pub struct Names {
     /// The long name. Most of the time this is the full path of the Rust type, starting
     /// with the crate name.
     pub long: &'static str,
+    /// The segments of the full path of the Rust type: the crate name, modules and
+    /// finally the ident. For types without a meaningful path (like primitives),
+    /// this is just one segment.
+    ///
+    /// This is useful for custom naming strategies, e.g. one that only keeps the
+    /// last module:
+    ///
+    /// ```
+    /// use jtd_derive::{Generator, JsonTypedef};
+    ///
+    /// mod api {
+    ///     pub mod v2 {
+    ///         #[derive(jtd_derive::JsonTypedef)]
+    ///         pub struct Foo {
+    ///             pub x: u32,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let root_schema = Generator::builder()
+    ///     .top_level_ref()
+    ///     .naming_custom(|names| names.path_segments.iter().rev().take(2).rev().cloned().collect::<Vec<_>>().join("."))
+    ///     .build()
+    ///     .into_root_schema::<api::v2::Foo>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(root_schema.definitions.keys().collect::<Vec<_>>(), ["v2.Foo"]);
+    /// ```
+    pub path_segments: Vec<&'static str>,
     /// Nullability.
     pub nullable: bool,
     /// Names of any type arguments applied to the generic Rust type.
+    ///
+    /// Derived impls only require `JsonTypedef` of type parameters the schema
+    /// depends on. The others are named with [`std::any::type_name`], whose
+    /// output isn't guaranteed to be stable between compiler versions, so
+    /// definition names of such instantiations can change with the toolchain.
     pub type_params: Vec<Names>,
     /// The values of constant arguments represented as strings.
     pub const_params: Vec<String>,
 }
+
+impl Names {
+    /// Render the names using the given naming strategy, the same way the
+    /// [`Generator`](crate::gen::Generator) would for definitions and refs.
+    /// This can only fail for custom strategies.
+    pub fn render(&self, strategy: &NamingStrategy) -> Result<String, GenError> {
+        strategy.apply(self)
+    }
+}
+
+impl fmt::Display for Names {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if f.alternate() { self.short } else { self.long };
+        f.write_str(name)?;
+
+        let mut params = self
+            .type_params
+            .iter()
+            .map(|p| {
+                if f.alternate() {
+                    format!("{:#}", p)
+                } else {
+                    p.to_string()
+                }
+            })
+            .chain(self.const_params.iter().cloned())
+            .peekable();
+
+        if params.peek().is_some() {
+            write!(f, "<{}>", params.collect::<Vec<_>>().join(", "))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+//! Named collections of schemas, e.g. for exporting or serving them.
+
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "axum")]
+mod axum;
+#[cfg(feature = "inventory")]
+mod collected;
+#[cfg(any(feature = "axum", feature = "actix"))]
+mod serve;
+
+#[cfg(feature = "inventory")]
+pub use collected::{all_types, RegisteredType};
+
+use std::collections::BTreeMap;
+
+use crate::schema::RootSchema;
+use crate::{GenError, Generator, JsonTypedef};
+
+/// A collection of root schemas, keyed by name.
+///
+/// # Example
+///
+/// ```
+/// use jtd_derive::{JsonTypedef, registry::SchemaRegistry};
+///
+/// #[derive(JsonTypedef)]
+/// struct Foo {
+///     x: u32,
+/// }
+///
+/// let mut registry = SchemaRegistry::new();
+/// registry.register::<Foo>().unwrap();
+///
+/// assert_eq!(registry.len(), 1);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SchemaRegistry {
+    schemas: BTreeMap<String, RootSchema>,
+}
+
+impl SchemaRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generate the schema for `T` using [`Generator::default()`] and add it
+    /// to the registry.
+    ///
+    /// The schema is registered under the full path of the type, the same way
+    /// the default naming strategy names definitions. Registering a type
+    /// under a name that's already taken replaces the old schema.
+    pub fn register<T: JsonTypedef>(&mut self) -> Result<&mut Self, GenError> {
+        self.register_with::<T>(Generator::default())
+    }
+
+    /// Like [`register`](Self::register), but uses the provided generator.
+    pub fn register_with<T: JsonTypedef>(&mut self, gen: Generator) -> Result<&mut Self, GenError> {
+        let schema = gen.into_root_schema::<T>()?;
+        self.schemas.insert(registry_name(&T::names()), schema);
+        Ok(self)
+    }
+
+    /// Register every type collected by [`all_types`], generating schemas with
+    /// [`Generator::default()`]. Only available with the `inventory` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jtd_derive::{JsonTypedef, registry::SchemaRegistry};
+    ///
+    /// #[derive(JsonTypedef)]
+    /// struct Foo {
+    ///     x: u32,
+    /// }
+    ///
+    /// let mut registry = SchemaRegistry::new();
+    /// registry.register_all().unwrap();
+    ///
+    /// assert!(registry.get("rust_out::Foo").is_some());
+    /// ```
+    #[cfg(feature = "inventory")]
+    pub fn register_all(&mut self) -> Result<&mut Self, GenError> {
+        self.register_all_with(Generator::default)
+    }
+
+    /// Like [`register_all`](Self::register_all), but uses generators produced
+    /// by the provided function, one per type.
+    #[cfg(feature = "inventory")]
+    pub fn register_all_with(
+        &mut self,
+        mut gen: impl FnMut() -> Generator,
+    ) -> Result<&mut Self, GenError> {
+        for ty in all_types() {
+            let schema = ty.root_schema(gen())?;
+            self.schemas.insert(registry_name(&ty.names()), schema);
+        }
+        Ok(self)
+    }
+
+    /// Add a schema under an arbitrary name. Returns the schema previously
+    /// registered under that name, if any.
+    pub fn insert(&mut self, name: impl Into<String>, schema: RootSchema) -> Option<RootSchema> {
+        self.schemas.insert(name.into(), schema)
+    }
+
+    /// Get a schema by name.
+    pub fn get(&self, name: &str) -> Option<&RootSchema> {
+        self.schemas.get(name)
+    }
+
+    /// The names of all registered schemas, in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.schemas.keys().map(String::as_str)
+    }
+
+    /// All registered schemas with their names, in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &RootSchema)> {
+        self.schemas.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// The number of registered schemas.
+    pub fn len(&self) -> usize {
+        self.schemas.len()
+    }
+
+    /// Returns `true` if no schemas are registered.
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+}
+
+fn registry_name(names: &crate::Names) -> String {
+    names.to_string()
+}
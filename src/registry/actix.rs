@@ -0,0 +1,57 @@
+use actix_web::http::header;
+use actix_web::{web, HttpRequest, HttpResponse, Scope};
+
+use super::serve::{Reply, Served};
+use super::SchemaRegistry;
+
+impl SchemaRegistry {
+    /// Turn the registry into an [`actix_web`] scope serving the schemas.
+    /// Only available with the `actix` feature.
+    ///
+    /// - `GET /schemas` responds with a JSON array of schema names.
+    /// - `GET /schemas/{name}` responds with the named schema.
+    ///
+    /// Responses carry an `ETag` and a `Cache-Control` header. Conditional
+    /// requests using `If-None-Match` are answered with `304 Not Modified`.
+    ///
+    /// Register the scope with `App::service`.
+    pub fn into_actix_scope(self) -> Scope {
+        web::scope("/schemas")
+            .app_data(web::Data::new(Served::new(&self)))
+            .route("", web::get().to(index))
+            .route("/{name}", web::get().to(schema))
+    }
+}
+
+async fn index(served: web::Data<Served>, req: HttpRequest) -> HttpResponse {
+    respond(served.index(if_none_match(&req)))
+}
+
+async fn schema(
+    served: web::Data<Served>,
+    name: web::Path<String>,
+    req: HttpRequest,
+) -> HttpResponse {
+    respond(served.schema(&name, if_none_match(&req)))
+}
+
+fn if_none_match(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+}
+
+fn respond(reply: Reply<'_>) -> HttpResponse {
+    match reply {
+        Reply::Ok(doc) => HttpResponse::Ok()
+            .insert_header((header::CONTENT_TYPE, Served::CONTENT_TYPE))
+            .insert_header((header::CACHE_CONTROL, Served::CACHE_CONTROL))
+            .insert_header((header::ETAG, doc.etag.as_str()))
+            .body(doc.body.clone()),
+        Reply::NotModified(doc) => HttpResponse::NotModified()
+            .insert_header((header::CACHE_CONTROL, Served::CACHE_CONTROL))
+            .insert_header((header::ETAG, doc.etag.as_str()))
+            .finish(),
+        Reply::NotFound => HttpResponse::NotFound().finish(),
+    }
+}
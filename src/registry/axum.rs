@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use axum::extract::Path;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+
+use super::serve::{Reply, Served};
+use super::SchemaRegistry;
+
+impl SchemaRegistry {
+    /// Turn the registry into an [`axum`] router serving the schemas.
+    /// Only available with the `axum` feature.
+    ///
+    /// - `GET /schemas` responds with a JSON array of schema names.
+    /// - `GET /schemas/{name}` responds with the named schema.
+    ///
+    /// Responses carry an `ETag` and a `Cache-Control` header. Conditional
+    /// requests using `If-None-Match` are answered with `304 Not Modified`.
+    ///
+    /// The returned router can be merged or nested into an application's router.
+    pub fn into_axum_router<S>(self) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        let served = Arc::new(Served::new(&self));
+        let index_served = served.clone();
+
+        Router::new()
+            .route(
+                "/schemas",
+                get(move |headers: HeaderMap| async move {
+                    respond(index_served.index(if_none_match(&headers)))
+                }),
+            )
+            .route(
+                "/schemas/{name}",
+                get(
+                    move |Path(name): Path<String>, headers: HeaderMap| async move {
+                        respond(served.schema(&name, if_none_match(&headers)))
+                    },
+                ),
+            )
+    }
+}
+
+fn if_none_match(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+}
+
+fn respond(reply: Reply<'_>) -> Response {
+    match reply {
+        Reply::Ok(doc) => (
+            [
+                (header::CONTENT_TYPE, Served::CONTENT_TYPE),
+                (header::CACHE_CONTROL, Served::CACHE_CONTROL),
+                (header::ETAG, doc.etag.as_str()),
+            ],
+            doc.body.clone(),
+        )
+            .into_response(),
+        Reply::NotModified(doc) => (
+            StatusCode::NOT_MODIFIED,
+            [
+                (header::CACHE_CONTROL, Served::CACHE_CONTROL),
+                (header::ETAG, doc.etag.as_str()),
+            ],
+        )
+            .into_response(),
+        Reply::NotFound => StatusCode::NOT_FOUND.into_response(),
+    }
+}
@@ -0,0 +1,90 @@
+//! Types registered automatically by the derive macro. Only available with the
+//! `inventory` feature.
+
+use crate::schema::RootSchema;
+use crate::{GenError, Generator, JsonTypedef, Names};
+
+/// A type that was registered for schema generation at compile time.
+///
+/// With the `inventory` feature enabled, `#[derive(JsonTypedef)]` registers
+/// every non-generic type it's used on. Generic types aren't registered, since
+/// there's no way to know which instantiations are of interest. Those (and
+/// types with a manual [`JsonTypedef`] impl) can be registered by hand using
+/// [`register_type!`](crate::register_type).
+#[derive(Debug, Clone, Copy)]
+pub struct RegisteredType {
+    names: fn() -> Names,
+    root_schema: fn(Generator) -> Result<RootSchema, GenError>,
+}
+
+impl RegisteredType {
+    /// Describe the type `T` for registration.
+    pub const fn new<T: JsonTypedef>() -> Self {
+        Self {
+            names: T::names,
+            root_schema: Generator::into_root_schema::<T>,
+        }
+    }
+
+    /// The names of the registered type.
+    pub fn names(&self) -> Names {
+        (self.names)()
+    }
+
+    /// Generate the root schema of the registered type using the provided
+    /// generator.
+    pub fn root_schema(&self, gen: Generator) -> Result<RootSchema, GenError> {
+        (self.root_schema)(gen)
+    }
+}
+
+inventory::collect!(RegisteredType);
+
+/// Register types so that they're returned by [`all_types`]. Only available with
+/// the `inventory` feature.
+///
+/// Derived non-generic types are registered automatically. This is meant for
+/// generic instantiations and types with manual [`JsonTypedef`] impls.
+///
+/// # Example
+///
+/// ```
+/// use jtd_derive::JsonTypedef;
+///
+/// #[derive(JsonTypedef)]
+/// struct Wrapper<T> {
+///     inner: T,
+/// }
+///
+/// jtd_derive::register_type!(Wrapper<u32>, Wrapper<String>);
+///
+/// assert!(jtd_derive::registry::all_types().any(|ty| ty.names().short == "Wrapper"));
+/// ```
+#[macro_export]
+macro_rules! register_type {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            $crate::__private::inventory::submit! {
+                $crate::registry::RegisteredType::new::<$ty>()
+            }
+        )+
+    };
+}
+
+/// All types registered in the binary, in no particular order.
+///
+/// # Example
+///
+/// ```
+/// use jtd_derive::JsonTypedef;
+///
+/// #[derive(JsonTypedef)]
+/// struct Foo {
+///     x: u32,
+/// }
+///
+/// assert!(jtd_derive::registry::all_types().any(|ty| ty.names().short == "Foo"));
+/// ```
+pub fn all_types() -> impl Iterator<Item = &'static RegisteredType> {
+    inventory::iter::<RegisteredType>.into_iter()
+}
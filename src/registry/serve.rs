@@ -0,0 +1,82 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash as _, Hasher as _};
+
+use super::SchemaRegistry;
+
+/// Pre-rendered registry contents, shared by the web framework integrations.
+///
+/// `GET /schemas` serves a JSON array of schema names, `GET /schemas/{name}`
+/// serves the schema itself.
+pub struct Served {
+    index: Document,
+    schemas: BTreeMap<String, Document>,
+}
+
+impl Served {
+    pub const CONTENT_TYPE: &'static str = "application/json";
+    pub const CACHE_CONTROL: &'static str = "public, max-age=300";
+
+    pub fn new(registry: &SchemaRegistry) -> Self {
+        let names: Vec<_> = registry.names().collect();
+
+        Self {
+            index: Document::new(&names),
+            schemas: registry
+                .iter()
+                .map(|(name, schema)| (name.to_string(), Document::new(schema)))
+                .collect(),
+        }
+    }
+
+    pub fn index(&self, if_none_match: Option<&str>) -> Reply<'_> {
+        Reply::new(&self.index, if_none_match)
+    }
+
+    pub fn schema(&self, name: &str, if_none_match: Option<&str>) -> Reply<'_> {
+        match self.schemas.get(name) {
+            Some(doc) => Reply::new(doc, if_none_match),
+            None => Reply::NotFound,
+        }
+    }
+}
+
+pub struct Document {
+    pub body: String,
+    pub etag: String,
+}
+
+impl Document {
+    fn new(value: &impl serde::Serialize) -> Self {
+        let body = serde_json::to_string(value).expect("schemas are always serializable");
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+
+        Self {
+            etag: format!("\"{:016x}\"", hasher.finish()),
+            body,
+        }
+    }
+}
+
+pub enum Reply<'a> {
+    Ok(&'a Document),
+    NotModified(&'a Document),
+    NotFound,
+}
+
+impl<'a> Reply<'a> {
+    fn new(doc: &'a Document, if_none_match: Option<&str>) -> Self {
+        let matches = if_none_match.is_some_and(|tags| {
+            tags.split(',')
+                .map(str::trim)
+                .any(|tag| tag == "*" || tag.trim_start_matches("W/") == doc.etag)
+        });
+
+        if matches {
+            Self::NotModified(doc)
+        } else {
+            Self::Ok(doc)
+        }
+    }
+}
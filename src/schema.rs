@@ -1,7 +1,8 @@
 //! The internal Rust representation of a [_JSON Typedef_](https://jsontypedef.com/)
 //! schema.
 
-use std::collections::BTreeMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use serde::Serialize;
 
@@ -21,6 +22,143 @@ pub struct RootSchema {
     pub schema: Schema,
 }
 
+impl RootSchema {
+    /// Remove definitions that can't be reached from the top-level schema by
+    /// following refs. The generator never produces those, but they can show
+    /// up after editing or merging schemas by hand.
+    pub fn prune_unused_definitions(&mut self) {
+        let mut reachable = HashSet::new();
+        let mut to_visit = vec![&self.schema];
+        while let Some(schema) = to_visit.pop() {
+            for r#ref in schema.refs() {
+                if reachable.insert(r#ref) {
+                    to_visit.extend(self.definitions.get(r#ref));
+                }
+            }
+        }
+
+        let reachable: HashSet<String> = reachable.into_iter().map(str::to_string).collect();
+        self.definitions.retain(|key, _| reachable.contains(key));
+    }
+
+    /// The names used in refs that don't match any definition, sorted and
+    /// without duplicates. These make the schema invalid. The generator never
+    /// produces them on its own, but they can appear after editing or merging
+    /// schemas by hand, or through hand-written [`JsonTypedef`](crate::JsonTypedef)
+    /// impls that create refs directly.
+    pub fn unresolved_refs(&self) -> Vec<&str> {
+        let refs: BTreeSet<_> = std::iter::once(&self.schema)
+            .chain(self.definitions.values())
+            .flat_map(Schema::refs)
+            .filter(|r#ref| !self.definitions.contains_key(*r#ref))
+            .collect();
+        refs.into_iter().collect()
+    }
+
+    /// Check that the schema is fit to be handed to _Typedef_ tooling, i.e.
+    /// that every ref points at a definition. See
+    /// [`unresolved_refs`](Self::unresolved_refs).
+    pub fn verify(&self) -> Result<(), VerifyError> {
+        VerifyError::check(self.unresolved_refs())
+    }
+
+    /// Rename a definition and rewrite every ref pointing at it.
+    ///
+    /// Fails if there's no definition called `old`, or if there already is
+    /// one called `new`. The schema is left untouched in that case.
+    pub fn rename_definition(&mut self, old: &str, new: &str) -> Result<(), RenameError> {
+        if self.definitions.contains_key(new) {
+            return Err(RenameError::AlreadyExists(new.to_string()));
+        }
+        let def = self
+            .definitions
+            .remove(old)
+            .ok_or_else(|| RenameError::NotFound(old.to_string()))?;
+        self.definitions.insert(new.to_string(), def);
+
+        for schema in std::iter::once(&mut self.schema).chain(self.definitions.values_mut()) {
+            schema.visit_mut(&mut |schema| {
+                if let SchemaType::Ref { r#ref } = &mut schema.ty {
+                    if r#ref == old {
+                        *r#ref = new.to_string();
+                    }
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned by [`RootSchema::rename_definition`].
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum RenameError {
+    /// The definition to rename doesn't exist.
+    #[error("there's no definition named \"{0}\"")]
+    NotFound(String),
+    /// The new name is already taken.
+    #[error("a definition named \"{0}\" already exists")]
+    AlreadyExists(String),
+}
+
+/// An error returned by [`RootSchema::verify`] and [`verify_json`].
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum VerifyError {
+    /// Some refs don't match any definition. Sorted and without duplicates.
+    #[error("refs to missing definitions: {}", .0.join(", "))]
+    UnresolvedRefs(Vec<String>),
+}
+
+impl VerifyError {
+    fn check<'a>(unresolved: impl IntoIterator<Item = &'a str>) -> Result<(), Self> {
+        let unresolved: Vec<_> = unresolved.into_iter().map(str::to_string).collect();
+        if unresolved.is_empty() {
+            Ok(())
+        } else {
+            Err(Self::UnresolvedRefs(unresolved))
+        }
+    }
+}
+
+/// Like [`RootSchema::verify`], but for a root schema in its JSON form, like
+/// one read from a file.
+pub fn verify_json(schema: &serde_json::Value) -> Result<(), VerifyError> {
+    fn collect_refs<'a>(schema: &'a serde_json::Value, refs: &mut BTreeSet<&'a str>) {
+        if let Some(serde_json::Value::String(r#ref)) = schema.get("ref") {
+            refs.insert(r#ref);
+        }
+        for key in ["elements", "values"] {
+            if let Some(child) = schema.get(key) {
+                collect_refs(child, refs);
+            }
+        }
+        for key in ["properties", "optionalProperties", "mapping"] {
+            if let Some(serde_json::Value::Object(children)) = schema.get(key) {
+                children
+                    .values()
+                    .for_each(|child| collect_refs(child, refs));
+            }
+        }
+    }
+
+    let empty = serde_json::Map::new();
+    let definitions = match schema.get("definitions") {
+        Some(serde_json::Value::Object(definitions)) => definitions,
+        _ => &empty,
+    };
+
+    let mut refs = BTreeSet::new();
+    collect_refs(schema, &mut refs);
+    definitions
+        .values()
+        .for_each(|definition| collect_refs(definition, &mut refs));
+
+    VerifyError::check(
+        refs.into_iter()
+            .filter(|r#ref| !definitions.contains_key(*r#ref)),
+    )
+}
+
 /// A [_JSON Typedef_](https://jsontypedef.com/) schema.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize)]
 pub struct Schema {
@@ -47,6 +185,224 @@ impl Default for Schema {
     }
 }
 
+impl Schema {
+    /// An [empty schema](https://jsontypedef.com/docs/jtd-in-5-minutes/#empty-schemas),
+    /// accepting any JSON data. Same as [`Schema::default()`].
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// A [type schema](https://jsontypedef.com/docs/jtd-in-5-minutes/#type-schemas).
+    pub fn of_type(r#type: TypeSchema) -> Self {
+        SchemaType::Type { r#type }.into()
+    }
+
+    /// An [enum schema](https://jsontypedef.com/docs/jtd-in-5-minutes/#enum-schemas).
+    pub fn enumeration(values: impl IntoIterator<Item = &'static str>) -> Self {
+        SchemaType::Enum {
+            r#enum: values.into_iter().collect(),
+        }
+        .into()
+    }
+
+    /// An [elements schema](https://jsontypedef.com/docs/jtd-in-5-minutes/#elements-schemas).
+    pub fn elements(elements: Schema) -> Self {
+        SchemaType::Elements {
+            elements: Box::new(elements),
+        }
+        .into()
+    }
+
+    /// A [values schema](https://jsontypedef.com/docs/jtd-in-5-minutes/#values-schemas).
+    pub fn values(values: Schema) -> Self {
+        SchemaType::Values {
+            values: Box::new(values),
+        }
+        .into()
+    }
+
+    /// A [discriminator schema](https://jsontypedef.com/docs/jtd-in-5-minutes/#discriminator-schemas).
+    /// The mapped schemas should be non-nullable properties schemas.
+    pub fn discriminator(
+        discriminator: &'static str,
+        mapping: impl IntoIterator<Item = (&'static str, Schema)>,
+    ) -> Self {
+        SchemaType::Discriminator {
+            discriminator,
+            mapping: mapping.into_iter().collect(),
+        }
+        .into()
+    }
+
+    /// A [ref schema](https://jsontypedef.com/docs/jtd-in-5-minutes/#ref-schemas).
+    pub fn reference(r#ref: impl Into<String>) -> Self {
+        SchemaType::Ref {
+            r#ref: r#ref.into(),
+        }
+        .into()
+    }
+
+    /// Start building a
+    /// [properties schema](https://jsontypedef.com/docs/jtd-in-5-minutes/#properties-schemas).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use jtd_derive::schema::{Schema, TypeSchema};
+    ///
+    /// let schema = Schema::properties()
+    ///     .property("id", Schema::of_type(TypeSchema::Uint32))
+    ///     .optional_property("name", Schema::of_type(TypeSchema::String))
+    ///     .build()
+    ///     .with_metadata("description", "a user");
+    ///
+    /// assert_eq!(
+    ///     serde_json::to_value(schema).unwrap(),
+    ///     serde_json::json! {{
+    ///         "metadata": { "description": "a user" },
+    ///         "properties": { "id": { "type": "uint32" } },
+    ///         "optionalProperties": { "name": { "type": "string" } },
+    ///     }}
+    /// );
+    /// ```
+    pub fn properties() -> PropertiesBuilder {
+        PropertiesBuilder::default()
+    }
+
+    /// The schemas directly nested in this one.
+    fn children(&self) -> Vec<&Schema> {
+        match &self.ty {
+            SchemaType::Elements { elements } => vec![elements],
+            SchemaType::Values { values } => vec![values],
+            SchemaType::Properties {
+                properties,
+                optional_properties,
+                ..
+            } => properties
+                .values()
+                .chain(optional_properties.values())
+                .collect(),
+            SchemaType::Discriminator { mapping, .. } => mapping.values().collect(),
+            SchemaType::Empty
+            | SchemaType::Type { .. }
+            | SchemaType::Enum { .. }
+            | SchemaType::Ref { .. } => vec![],
+        }
+    }
+
+    /// The schemas directly nested in this one, mutably.
+    fn children_mut(&mut self) -> Vec<&mut Schema> {
+        match &mut self.ty {
+            SchemaType::Elements { elements } => vec![elements],
+            SchemaType::Values { values } => vec![values],
+            SchemaType::Properties {
+                properties,
+                optional_properties,
+                ..
+            } => properties
+                .values_mut()
+                .chain(optional_properties.values_mut())
+                .collect(),
+            SchemaType::Discriminator { mapping, .. } => mapping.values_mut().collect(),
+            SchemaType::Empty
+            | SchemaType::Type { .. }
+            | SchemaType::Enum { .. }
+            | SchemaType::Ref { .. } => vec![],
+        }
+    }
+
+    /// Call `f` on this schema and every schema nested in it.
+    pub(crate) fn visit_mut(&mut self, f: &mut impl FnMut(&mut Schema)) {
+        f(self);
+        for child in self.children_mut() {
+            child.visit_mut(f);
+        }
+    }
+
+    /// All the refs in this schema and the schemas nested in it, in no
+    /// particular order. Definitions aren't followed.
+    pub(crate) fn refs(&self) -> Vec<&str> {
+        let mut refs = vec![];
+        let mut to_visit = vec![self];
+        while let Some(schema) = to_visit.pop() {
+            if let SchemaType::Ref { r#ref } = &schema.ty {
+                refs.push(r#ref.as_str());
+            }
+            to_visit.extend(schema.children());
+        }
+        refs
+    }
+
+    /// Set whether this schema is nullable.
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
+    /// Add a metadata entry, replacing any previous entry with the same key.
+    pub fn with_metadata(mut self, key: &'static str, value: impl Into<serde_json::Value>) -> Self {
+        self.metadata.0.insert(key.into(), value.into());
+        self
+    }
+
+    /// Add a [`jtd-fuzz`](https://github.com/jsontypedef/json-typedef-fuzz)
+    /// hint, like `"en_us.internet.email"`, telling it what kind of
+    /// realistic data to generate for this schema.
+    pub fn with_fuzz_hint(self, hint: impl Into<String>) -> Self {
+        self.with_metadata(Metadata::FUZZ_HINT, hint.into())
+    }
+}
+
+impl From<SchemaType> for Schema {
+    fn from(ty: SchemaType) -> Self {
+        Self {
+            ty,
+            ..Self::default()
+        }
+    }
+}
+
+/// Builder for a
+/// [properties schema](https://jsontypedef.com/docs/jtd-in-5-minutes/#properties-schemas).
+/// Created using [`Schema::properties()`].
+#[derive(Default, Debug)]
+pub struct PropertiesBuilder {
+    properties: BTreeMap<&'static str, Schema>,
+    optional_properties: BTreeMap<&'static str, Schema>,
+    additional_properties: bool,
+}
+
+impl PropertiesBuilder {
+    /// Add a required property.
+    pub fn property(&mut self, name: &'static str, schema: Schema) -> &mut Self {
+        self.properties.insert(name, schema);
+        self
+    }
+
+    /// Add an optional property.
+    pub fn optional_property(&mut self, name: &'static str, schema: Schema) -> &mut Self {
+        self.optional_properties.insert(name, schema);
+        self
+    }
+
+    /// Set whether properties not listed in the schema are allowed. They're
+    /// not by default.
+    pub fn additional_properties(&mut self, allowed: bool) -> &mut Self {
+        self.additional_properties = allowed;
+        self
+    }
+
+    /// Finalize the schema.
+    pub fn build(&mut self) -> Schema {
+        SchemaType::Properties {
+            properties: std::mem::take(&mut self.properties),
+            optional_properties: std::mem::take(&mut self.optional_properties),
+            additional_properties: self.additional_properties,
+        }
+        .into()
+    }
+}
+
 /// The 8 forms a schema can take. For more info
 /// [see here](https://jsontypedef.com/docs/jtd-in-5-minutes/#what-is-a-json-type-definition-schema).
 #[derive(Debug, PartialEq, Eq, Clone, Serialize)]
@@ -124,13 +480,44 @@ impl TypeSchema {
 /// Metadata is a freeform map and a way to extend Typedef. The spec doesn't specify
 /// what might go in there. By default, `jtd_derive` doesn't generate any metadata.
 #[derive(Default, Debug, PartialEq, Eq, Clone, Serialize)]
-pub struct Metadata(BTreeMap<&'static str, serde_json::Value>);
+pub struct Metadata(BTreeMap<Cow<'static, str>, serde_json::Value>);
 
 impl Metadata {
     /// Construct a [`Metadata`] object from something that can be converted
     /// to the appropriate hashmap.
     pub fn from_map(m: impl Into<BTreeMap<&'static str, serde_json::Value>>) -> Self {
-        Self(m.into())
+        Self(m.into().into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+
+    /// The key `jtd-fuzz` reads hints from. See [`Schema::with_fuzz_hint`].
+    pub const FUZZ_HINT: &'static str = "fuzzHint";
+
+    /// The `jtd-fuzz` hint, if there is one. See [`Schema::with_fuzz_hint`].
+    pub fn fuzz_hint(&self) -> Option<&str> {
+        self.get(Self::FUZZ_HINT)
+            .and_then(serde_json::Value::as_str)
+    }
+
+    /// Get the entry under the given key.
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.0.get(key)
+    }
+
+    /// Move the entries with the given keys into an object under `namespace`.
+    pub(crate) fn move_into_namespace(&mut self, namespace: &str, keys: &[&str]) {
+        let mut nested = serde_json::Map::new();
+        self.0.retain(|key, value| {
+            if keys.contains(&&**key) {
+                nested.insert(key.to_string(), value.take());
+                false
+            } else {
+                true
+            }
+        });
+
+        if !nested.is_empty() {
+            self.0.insert(namespace.to_owned().into(), nested.into());
+        }
     }
 
     /// Returns `true` if there are no metadata entries.
@@ -139,12 +526,9 @@ impl Metadata {
     }
 }
 
-impl<A> Extend<A> for Metadata
-where
-    BTreeMap<&'static str, serde_json::Value>: Extend<A>,
-{
-    fn extend<T: IntoIterator<Item = A>>(&mut self, iter: T) {
-        self.0.extend(iter)
+impl<K: Into<Cow<'static, str>>> Extend<(K, serde_json::Value)> for Metadata {
+    fn extend<T: IntoIterator<Item = (K, serde_json::Value)>>(&mut self, iter: T) {
+        self.0.extend(iter.into_iter().map(|(k, v)| (k.into(), v)))
     }
 }
 
@@ -601,4 +985,156 @@ mod tests {
             })
         )
     }
+
+    #[test]
+    fn constructors() {
+        assert_eq!(
+            Schema::elements(Schema::enumeration(["FOO", "BAR"]).with_nullable(true)),
+            Schema {
+                ty: SchemaType::Elements {
+                    elements: Box::new(Schema {
+                        ty: SchemaType::Enum {
+                            r#enum: vec!["FOO", "BAR"],
+                        },
+                        nullable: true,
+                        ..Schema::default()
+                    }),
+                },
+                ..Schema::default()
+            }
+        );
+
+        assert_eq!(
+            Schema::discriminator(
+                "type",
+                [(
+                    "a",
+                    Schema::properties()
+                        .property("x", Schema::reference("X"))
+                        .additional_properties(true)
+                        .build()
+                )]
+            ),
+            Schema {
+                ty: SchemaType::Discriminator {
+                    discriminator: "type",
+                    mapping: [(
+                        "a",
+                        Schema {
+                            ty: SchemaType::Properties {
+                                properties: [(
+                                    "x",
+                                    Schema {
+                                        ty: SchemaType::Ref { r#ref: "X".into() },
+                                        ..Schema::default()
+                                    }
+                                )]
+                                .into(),
+                                optional_properties: BTreeMap::new(),
+                                additional_properties: true,
+                            },
+                            ..Schema::default()
+                        }
+                    )]
+                    .into(),
+                },
+                ..Schema::default()
+            }
+        );
+    }
+
+    #[test]
+    fn prune_unused_definitions() {
+        let mut root = RootSchema {
+            definitions: [
+                ("a".to_string(), Schema::elements(Schema::reference("b"))),
+                ("b".to_string(), Schema::of_type(TypeSchema::Uint8)),
+                ("c".to_string(), Schema::reference("d")),
+                ("d".to_string(), Schema::reference("c")),
+            ]
+            .into(),
+            schema: Schema::properties()
+                .property("a", Schema::reference("a"))
+                .build(),
+        };
+
+        root.prune_unused_definitions();
+        assert_eq!(
+            root.definitions.keys().collect::<Vec<_>>(),
+            [&"a".to_string(), &"b".to_string()]
+        );
+    }
+
+    #[test]
+    fn rename_definition() {
+        let mut root = RootSchema {
+            definitions: [
+                ("a".to_string(), Schema::elements(Schema::reference("a"))),
+                ("b".to_string(), Schema::of_type(TypeSchema::Uint8)),
+            ]
+            .into(),
+            schema: Schema::properties()
+                .property("a", Schema::reference("a"))
+                .build(),
+        };
+
+        assert_eq!(
+            root.rename_definition("a", "b"),
+            Err(RenameError::AlreadyExists("b".to_string()))
+        );
+        assert_eq!(
+            root.rename_definition("x", "y"),
+            Err(RenameError::NotFound("x".to_string()))
+        );
+
+        root.rename_definition("a", "c").unwrap();
+        assert_eq!(
+            serde_json::to_value(&root).unwrap(),
+            json!({
+                "definitions": {
+                    "b": { "type": "uint8" },
+                    "c": { "elements": { "ref": "c" } },
+                },
+                "properties": {
+                    "a": { "ref": "c" },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn unresolved_refs() {
+        let root = RootSchema {
+            definitions: [
+                ("a".to_string(), Schema::values(Schema::reference("c"))),
+                ("b".to_string(), Schema::reference("a")),
+            ]
+            .into(),
+            schema: Schema::properties()
+                .property("a", Schema::reference("a"))
+                .property("c", Schema::reference("c"))
+                .property("d", Schema::reference("d"))
+                .build(),
+        };
+
+        assert_eq!(root.unresolved_refs(), ["c", "d"]);
+
+        let unresolved = Err(VerifyError::UnresolvedRefs(vec!["c".into(), "d".into()]));
+        assert_eq!(root.verify(), unresolved);
+        assert_eq!(
+            verify_json(&serde_json::to_value(&root).unwrap()),
+            unresolved
+        );
+        assert_eq!(
+            root.verify().unwrap_err().to_string(),
+            "refs to missing definitions: c, d"
+        );
+
+        let root = RootSchema {
+            definitions: [("a".to_string(), Schema::elements(Schema::reference("a")))].into(),
+            schema: Schema::reference("a"),
+        };
+        assert_eq!(root.verify(), Ok(()));
+        assert_eq!(verify_json(&serde_json::to_value(&root).unwrap()), Ok(()));
+    }
 }
@@ -0,0 +1,242 @@
+//! Helpers for testing schemas. Only available with the `testing` feature.
+
+use std::path::Path;
+
+use arbitrary::{Arbitrary, Unstructured};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::schema::RootSchema;
+use crate::{Generator, JsonTypedef, SchemaMode};
+
+/// The environment variable that, when set to a non-empty value other than `0`,
+/// makes snapshot assertions overwrite the stored snapshots instead of
+/// comparing against them.
+pub const BLESS_ENV_VAR: &str = "JTD_DERIVE_BLESS";
+
+/// Generate the schema for a type and compare it against a snapshot stored in
+/// a file. The path is relative to the directory of the crate being tested.
+///
+/// The snapshot is the pretty-printed JSON form of the schema. On mismatch,
+/// the assertion panics with a line diff between the stored and the actual
+/// schema. To create or update snapshots, run the tests with the
+/// `JTD_DERIVE_BLESS=1` environment variable set.
+///
+/// By default, schemas are generated using [`Generator::default()`]. A custom
+/// generator can be provided as the third argument.
+///
+/// # Example
+///
+/// ```no_run
+/// use jtd_derive::{assert_schema_snapshot, JsonTypedef, Generator};
+///
+/// #[derive(JsonTypedef)]
+/// struct Foo {
+///     x: u32,
+/// }
+///
+/// assert_schema_snapshot!(Foo, "tests/snapshots/foo.json");
+/// assert_schema_snapshot!(
+///     Foo,
+///     "tests/snapshots/foo_ref.json",
+///     Generator::builder().top_level_ref().build()
+/// );
+/// ```
+#[macro_export]
+macro_rules! assert_schema_snapshot {
+    ($ty:ty, $path:expr $(,)?) => {
+        $crate::assert_schema_snapshot!($ty, $path, $crate::Generator::default())
+    };
+    ($ty:ty, $path:expr, $gen:expr $(,)?) => {
+        $crate::testing::assert_snapshot::<$ty>(
+            $gen,
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($path),
+        )
+    };
+}
+
+/// The function behind [`assert_schema_snapshot!`]. Prefer the macro, which
+/// resolves the path relative to the tested crate.
+#[track_caller]
+pub fn assert_snapshot<T: JsonTypedef>(gen: Generator, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    let schema = gen
+        .into_root_schema::<T>()
+        .unwrap_or_else(|e| panic!("failed to generate the schema: {}", e));
+    let actual = render(&schema);
+
+    if blessing() {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .unwrap_or_else(|e| panic!("failed to create {}: {}", dir.display(), e));
+        }
+        std::fs::write(path, actual)
+            .unwrap_or_else(|e| panic!("failed to write {}: {}", path.display(), e));
+        return;
+    }
+
+    let expected = match std::fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(e) => panic!(
+            "failed to read snapshot {}: {}\n\
+             hint: run with {}=1 to create it",
+            path.display(),
+            e,
+            BLESS_ENV_VAR
+        ),
+    };
+
+    if expected != actual {
+        panic!(
+            "schema doesn't match snapshot {}\n\
+             hint: run with {}=1 to update it\n\n{}",
+            path.display(),
+            BLESS_ENV_VAR,
+            line_diff(&expected, &actual)
+        );
+    }
+}
+
+fn blessing() -> bool {
+    std::env::var(BLESS_ENV_VAR).is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+fn render(schema: &RootSchema) -> String {
+    let mut rendered =
+        serde_json::to_string_pretty(schema).expect("schemas are always serializable");
+    rendered.push('\n');
+    rendered
+}
+
+/// A minimal LCS-based line diff. Snapshots are small, so the quadratic
+/// approach is fine.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let old: Vec<_> = expected.lines().collect();
+    let new: Vec<_> = actual.lines().collect();
+
+    // lcs[i][j] is the length of the LCS of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::from("--- snapshot\n+++ actual\n");
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push_str(&format!("  {}\n", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push_str(&format!("- {}\n", old[i]));
+            i += 1;
+        } else {
+            out.push_str(&format!("+ {}\n", new[j]));
+            j += 1;
+        }
+    }
+
+    out
+}
+
+/// Generate random values of a type, serialize them with `serde_json` and
+/// validate the results against the type's schema. Panics on the first value
+/// that doesn't match the schema or fails to deserialize back.
+///
+/// This is meant to catch divergences between how `serde` (de)serializes a type
+/// and the schema `jtd-derive` generates for it, e.g. due to `serde` attributes
+/// that aren't reflected in the schema.
+///
+/// Values are produced using the type's [`Arbitrary`] impl, fed with
+/// pseudo-random data from a fixed seed, so runs are reproducible.
+///
+/// Values are checked against the [`SchemaMode::Serialize`] schema, whatever
+/// mode `gen` was built with, since that's the schema serialized values have
+/// to match.
+///
+/// # Example
+///
+/// ```no_run
+/// use jtd_derive::{JsonTypedef, Generator};
+///
+/// #[derive(JsonTypedef, serde::Serialize, serde::Deserialize, arbitrary::Arbitrary)]
+/// struct Foo {
+///     x: u32,
+/// }
+///
+/// jtd_derive::testing::assert_round_trip::<Foo>(Generator::default(), 256);
+/// ```
+#[track_caller]
+pub fn assert_round_trip<T>(mut gen: Generator, iterations: usize)
+where
+    T: JsonTypedef + Serialize + DeserializeOwned + for<'a> Arbitrary<'a>,
+{
+    gen.set_mode(SchemaMode::Serialize);
+    let schema = gen
+        .into_root_schema::<T>()
+        .unwrap_or_else(|e| panic!("failed to generate the schema: {}", e));
+    let schema = serde_json::from_value(serde_json::to_value(&schema).unwrap())
+        .expect("generated schemas are valid Typedef schemas");
+    let schema = jtd::Schema::from_serde_schema(schema)
+        .expect("generated schemas are valid Typedef schemas");
+
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    let mut data = vec![];
+
+    for iteration in 0..iterations {
+        data.clear();
+        data.extend((0..(iteration % 64 + 1) * 16).map(|_| rng.next() as u8));
+
+        let value = match T::arbitrary(&mut Unstructured::new(&data)) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+        let json = serde_json::to_value(&value)
+            .unwrap_or_else(|e| panic!("failed to serialize a value: {}", e));
+
+        let errors = jtd::validate(&schema, &json, jtd::ValidateOptions::new())
+            .unwrap_or_else(|e| panic!("failed to validate {}: {:?}", json, e));
+        if !errors.is_empty() {
+            let errors: Vec<_> = errors
+                .into_iter()
+                .map(|e| {
+                    let (instance, schema) = e.into_owned_paths();
+                    format!("/{} rejected by /{}", instance.join("/"), schema.join("/"))
+                })
+                .collect();
+            panic!(
+                "serialized value doesn't match the schema (iteration {})\n\
+                 value: {}\n{}",
+                iteration,
+                json,
+                errors.join("\n")
+            );
+        }
+
+        if let Err(e) = serde_json::from_value::<T>(json.clone()) {
+            panic!(
+                "serialized value can't be deserialized back (iteration {})\n\
+                 value: {}\nerror: {}",
+                iteration, json, e
+            );
+        }
+    }
+}
+
+/// A tiny PRNG. Good enough to feed [`Unstructured`], and saves us a dependency.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -27,8 +27,168 @@ pub trait JsonTypedef {
     /// [_Typedef_](https://jsontypedef.com/) schema.
     /// Mostly used to generate a name for the top-level definitions.
     fn names() -> Names;
+
+    /// The file and line the type is defined at, if known. The derive macro
+    /// provides this in debug builds. See
+    /// [`GeneratorBuilder::source_locations`](crate::gen::GeneratorBuilder::source_locations).
+    fn source_location() -> Option<(&'static str, u32)> {
+        None
+    }
+}
+
+/// Types that serialize as valid JSON object keys, and so can be used as
+/// keys of maps like [`HashMap`] and [`BTreeMap`].
+///
+/// This is implemented for strings, integers (which `serde_json` turns into
+/// strings) and other types that serialize as strings. The derive macro
+/// implements it for enums with only unit variants, and for newtypes over
+/// other keys marked with `#[typedef(key)]`.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use jtd_derive::JsonTypedef;
+///
+/// #[derive(JsonTypedef, PartialEq, Eq, Hash)]
+/// #[typedef(key)]
+/// struct UserId(String);
+///
+/// #[derive(JsonTypedef)]
+/// struct Users {
+///     names: HashMap<UserId, String>,
+/// }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be used as a JSON object key",
+    label = "this type doesn't implement `JsonTypedefKey`",
+    note = "map keys must serialize as strings; see the `JsonTypedefKey` docs"
+)]
+pub trait JsonTypedefKey: JsonTypedef {}
+
+/// Implement [`JsonTypedef`] by hand for the common cases, when the type
+/// can't derive it (like a wrapper with custom serde impls):
+///
+/// - `Type => string` - the type is (de)serialized as a string,
+/// - `Type => transparent(Inner)` - the type is (de)serialized as `Inner`,
+/// - `Type => elements(Inner)` - the type is (de)serialized as an array of
+///   `Inner`.
+///
+/// Type parameters go in front, like `impl<T> Type<T> => transparent(T)`,
+/// and are required to implement [`JsonTypedef`]. The schema is always
+/// inlined, like that of the inner type; derive [`JsonTypedef`] if you need
+/// a definition.
+///
+/// # Example
+///
+/// ```
+/// use jtd_derive::{impl_json_typedef, Generator};
+///
+/// struct Email(String);
+/// struct Meters(f32);
+/// struct Tags<T>(Vec<T>);
+///
+/// impl_json_typedef!(Email => string);
+/// impl_json_typedef!(Meters => transparent(f32));
+/// impl_json_typedef!(impl<T> Tags<T> => elements(T));
+///
+/// let schema = Generator::default().into_root_schema::<Tags<Email>>().unwrap();
+/// assert_eq!(
+///     serde_json::to_value(schema).unwrap(),
+///     serde_json::json!({ "elements": { "type": "string" } }),
+/// );
+/// ```
+#[macro_export]
+macro_rules! impl_json_typedef {
+    (@impl [$($param:ident),*] $ty:ty, string) => {
+        $crate::impl_json_typedef!(@impl [$($param),*] $ty, transparent(::std::string::String));
+    };
+    (@impl [$($param:ident),*] $ty:ty, elements($inner:ty)) => {
+        $crate::impl_json_typedef!(@impl [$($param),*] $ty, transparent(::std::vec::Vec<$inner>));
+    };
+    (@impl [$($param:ident),*] $ty:ty, transparent($inner:ty)) => {
+        impl<$($param: $crate::JsonTypedef),*> $crate::JsonTypedef for $ty {
+            fn schema(gen: &mut $crate::Generator) -> $crate::schema::Schema {
+                gen.sub_schema::<$inner>()
+            }
+
+            fn referenceable() -> bool {
+                false
+            }
+
+            fn names() -> $crate::Names {
+                <$inner as $crate::JsonTypedef>::names()
+            }
+        }
+    };
+    (impl<$($param:ident),+ $(,)?> $ty:ty => $($kind:tt)+) => {
+        $crate::impl_json_typedef!(@impl [$($param),+] $ty, $($kind)+);
+    };
+    ($ty:ty => $($kind:tt)+) => {
+        $crate::impl_json_typedef!(@impl [] $ty, $($kind)+);
+    };
+}
+
+macro_rules! impl_keys {
+    ($($in:ty),*) => {
+        $(impl JsonTypedefKey for $in {})*
+    };
 }
 
+impl_keys!(
+    bool,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    char,
+    String,
+    str,
+    std::num::NonZeroU8,
+    std::num::NonZeroU16,
+    std::num::NonZeroU32,
+    std::num::NonZeroU64,
+    std::num::NonZeroU128,
+    std::num::NonZeroUsize,
+    std::num::NonZeroI8,
+    std::num::NonZeroI16,
+    std::num::NonZeroI32,
+    std::num::NonZeroI64,
+    std::num::NonZeroI128,
+    std::num::NonZeroIsize,
+    std::net::IpAddr,
+    std::net::Ipv4Addr,
+    std::net::Ipv6Addr,
+    std::net::SocketAddr,
+    std::net::SocketAddrV4,
+    std::net::SocketAddrV6,
+    std::path::Path,
+    std::path::PathBuf
+);
+
+#[cfg(feature = "url")]
+impl_keys!(url::Url);
+
+#[cfg(feature = "log")]
+impl_keys!(log::Level, log::LevelFilter);
+
+#[cfg(feature = "semver")]
+impl_keys!(semver::Version);
+
+#[cfg(feature = "tracing")]
+impl_keys!(tracing_core::Level, tracing_core::metadata::LevelFilter);
+
+impl<T: JsonTypedefKey + ?Sized> JsonTypedefKey for &T {}
+impl<T: JsonTypedefKey + ?Sized> JsonTypedefKey for Box<T> {}
+impl<T: JsonTypedefKey + ?Sized> JsonTypedefKey for std::rc::Rc<T> {}
+impl<T: JsonTypedefKey + ?Sized> JsonTypedefKey for std::sync::Arc<T> {}
+
 macro_rules! impl_primitives {
 	($($in:ty => $out:ident),*) => {
 		$(
@@ -50,6 +210,7 @@ macro_rules! impl_primitives {
                     Names {
                         short: TypeSchema::$out.name(),
                         long: TypeSchema::$out.name(),
+                        path_segments: vec![TypeSchema::$out.name()],
                         nullable: false,
                         type_params: vec![],
                         const_params: vec![],
@@ -78,11 +239,73 @@ impl_primitives! {
     atomic::AtomicI8 => Int8,
     atomic::AtomicI16 => Int16,
     atomic::AtomicI32 => Int32,
-    char => String,
     String => String,
     str => String
 }
 
+// No Typedef equivalent, see `GeneratorBuilder::integers_64` and
+// `GeneratorBuilder::integers_128`
+macro_rules! impl_wide_integers {
+	($($in:ty => $name:ident => $bits:literal => $signed:literal),*) => {
+		$(
+            impl JsonTypedef for $in {
+                fn schema(gen: &mut Generator) -> Schema {
+                    gen.wide_integer_schema(stringify!($in), $bits, $signed)
+                }
+
+                fn referenceable() -> bool {
+                    false
+                }
+
+                fn names() -> Names {
+                    Names {
+                        short: stringify!($name),
+                        long: stringify!($name),
+                        path_segments: vec![stringify!($name)],
+                        nullable: false,
+                        type_params: vec![],
+                        const_params: vec![],
+                    }
+                }
+            }
+        )*
+	};
+}
+
+impl_wide_integers! {
+    u64 => uint64 => 64 => false,
+    i64 => int64 => 64 => true,
+    usize => uint64 => 64 => false,
+    isize => int64 => 64 => true,
+    atomic::AtomicU64 => uint64 => 64 => false,
+    atomic::AtomicI64 => int64 => 64 => true,
+    atomic::AtomicUsize => uint64 => 64 => false,
+    atomic::AtomicIsize => int64 => 64 => true,
+    u128 => uint128 => 128 => false,
+    i128 => int128 => 128 => true
+}
+
+impl JsonTypedef for char {
+    fn schema(gen: &mut Generator) -> Schema {
+        let schema = Schema::of_type(TypeSchema::String);
+        if gen.char_length() {
+            schema
+                .with_metadata("minLength", 1)
+                .with_metadata("maxLength", 1)
+        } else {
+            schema
+        }
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        str::names()
+    }
+}
+
 // Distinct types due to additional constraints
 macro_rules! impl_wrappers {
 	($($($path_parts:ident)::+ => $in:ident => $out:ident),*) => {
@@ -104,7 +327,8 @@ macro_rules! impl_wrappers {
                 fn names() -> Names {
                     Names {
                         short: stringify!($in),
-                        long: stringify!($($path_parts)::+::$in),
+                        long: concat!($(stringify!($path_parts), "::",)+ stringify!($in)),
+                        path_segments: vec![$(stringify!($path_parts),)+ stringify!($in)],
                         nullable: false,
                         type_params: vec![],
                         const_params: vec![],
@@ -133,11 +357,269 @@ impl_wrappers! {
     std::path => Path => String
 }
 
+// Like the wrappers above, but subject to the wide integer strategies
+macro_rules! impl_wide_wrappers {
+	($($($path_parts:ident)::+ => $in:ident => $bits:literal => $signed:literal),*) => {
+		$(
+            impl JsonTypedef for $($path_parts)::+::$in {
+                fn schema(gen: &mut Generator) -> Schema {
+                    gen.wide_integer_schema(stringify!($in), $bits, $signed)
+                }
+
+                fn referenceable() -> bool {
+                    true
+                }
+
+                fn names() -> Names {
+                    Names {
+                        short: stringify!($in),
+                        long: concat!($(stringify!($path_parts), "::",)+ stringify!($in)),
+                        path_segments: vec![$(stringify!($path_parts),)+ stringify!($in)],
+                        nullable: false,
+                        type_params: vec![],
+                        const_params: vec![],
+                    }
+                }
+            }
+        )*
+	};
+}
+
+impl_wide_wrappers! {
+    std::num => NonZeroU64 => 64 => false,
+    std::num => NonZeroI64 => 64 => true,
+    std::num => NonZeroUsize => 64 => false,
+    std::num => NonZeroIsize => 64 => true,
+    std::num => NonZeroU128 => 128 => false,
+    std::num => NonZeroI128 => 128 => true
+}
+
 #[cfg(feature = "url")]
 impl_wrappers! {
     url => Url => String
 }
 
+// Foreign types that serialize as strings in a particular format
+#[cfg(feature = "semver")]
+macro_rules! impl_formatted_strings {
+	($($($path_parts:ident)::+ => $in:ident => $format:literal),*) => {
+		$(
+            impl JsonTypedef for $($path_parts)::+::$in {
+                fn schema(_: &mut Generator) -> Schema {
+                    Schema::of_type(TypeSchema::String).with_metadata("format", $format)
+                }
+
+                fn referenceable() -> bool {
+                    true
+                }
+
+                fn names() -> Names {
+                    Names {
+                        short: stringify!($in),
+                        long: concat!($(stringify!($path_parts), "::",)+ stringify!($in)),
+                        path_segments: vec![$(stringify!($path_parts),)+ stringify!($in)],
+                        nullable: false,
+                        type_params: vec![],
+                        const_params: vec![],
+                    }
+                }
+            }
+        )*
+	};
+}
+
+#[cfg(feature = "semver")]
+impl_formatted_strings! {
+    semver => Version => "semver",
+    semver => VersionReq => "semver-requirement"
+}
+
+// Foreign enums that serialize as one of a fixed set of strings
+#[cfg(any(feature = "log", feature = "tracing"))]
+macro_rules! impl_enums {
+	($($($path_parts:ident)::+ => $in:ident => [$($variant:literal),*]),*) => {
+		$(
+            impl JsonTypedef for $($path_parts)::+::$in {
+                fn schema(_: &mut Generator) -> Schema {
+                    Schema::enumeration([$($variant),*])
+                }
+
+                fn referenceable() -> bool {
+                    true
+                }
+
+                fn names() -> Names {
+                    Names {
+                        short: stringify!($in),
+                        long: concat!($(stringify!($path_parts), "::",)+ stringify!($in)),
+                        path_segments: vec![$(stringify!($path_parts),)+ stringify!($in)],
+                        nullable: false,
+                        type_params: vec![],
+                        const_params: vec![],
+                    }
+                }
+            }
+        )*
+	};
+}
+
+#[cfg(feature = "log")]
+impl_enums! {
+    log => Level => ["ERROR", "WARN", "INFO", "DEBUG", "TRACE"],
+    log => LevelFilter => ["OFF", "ERROR", "WARN", "INFO", "DEBUG", "TRACE"]
+}
+
+// `tracing` re-exports these from `tracing_core`. They don't implement
+// `Serialize`, but are typically (de)serialized through their `Display` and
+// `FromStr` impls.
+#[cfg(feature = "tracing")]
+impl_enums! {
+    tracing_core => Level => ["TRACE", "DEBUG", "INFO", "WARN", "ERROR"],
+    tracing_core::metadata => LevelFilter => ["off", "trace", "debug", "info", "warn", "error"]
+}
+
+/// `serde` serializes timestamps as the time since the Unix epoch, split into
+/// `u64` seconds and `u32` nanoseconds. The seconds are subject to
+/// [`GeneratorBuilder::integers_64`](crate::gen::GeneratorBuilder::integers_64).
+impl JsonTypedef for std::time::SystemTime {
+    fn schema(gen: &mut Generator) -> Schema {
+        Schema::properties()
+            .property("secs_since_epoch", gen.sub_schema::<u64>())
+            .property("nanos_since_epoch", gen.sub_schema::<u32>())
+            .build()
+    }
+
+    fn referenceable() -> bool {
+        true
+    }
+
+    fn names() -> Names {
+        Names {
+            short: "SystemTime",
+            long: "std::time::SystemTime",
+            path_segments: vec!["std", "time", "SystemTime"],
+            nullable: false,
+            type_params: vec![],
+            const_params: vec![],
+        }
+    }
+}
+
+/// `humantime_serde` serializes durations as strings like `"2h 30m"`. The schema
+/// says as much in its `format` metadata.
+#[cfg(feature = "humantime")]
+impl JsonTypedef for humantime_serde::Serde<std::time::Duration> {
+    fn schema(_: &mut Generator) -> Schema {
+        Schema::of_type(TypeSchema::String).with_metadata("format", "humantime-duration")
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        Names {
+            short: "Serde<Duration>",
+            long: "humantime_serde::Serde<std::time::Duration>",
+            path_segments: vec!["humantime_serde", "Serde"],
+            nullable: false,
+            type_params: vec![],
+            const_params: vec![],
+        }
+    }
+}
+
+/// `humantime_serde` serializes timestamps as RFC 3339 strings.
+#[cfg(feature = "humantime")]
+impl JsonTypedef for humantime_serde::Serde<std::time::SystemTime> {
+    fn schema(_: &mut Generator) -> Schema {
+        Schema::of_type(TypeSchema::String).with_metadata("format", "date-time")
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        Names {
+            short: "Serde<SystemTime>",
+            long: "humantime_serde::Serde<std::time::SystemTime>",
+            path_segments: vec!["humantime_serde", "Serde"],
+            nullable: false,
+            type_params: vec![],
+            const_params: vec![],
+        }
+    }
+}
+
+/// Freeform JSON, so anything goes.
+impl JsonTypedef for serde_json::Value {
+    fn schema(_: &mut Generator) -> Schema {
+        Schema::empty()
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        Names {
+            short: "Value",
+            long: "serde_json::Value",
+            path_segments: vec!["serde_json", "Value"],
+            nullable: false,
+            type_params: vec![],
+            const_params: vec![],
+        }
+    }
+}
+
+/// A JSON object with freeform values.
+impl JsonTypedef for serde_json::Map<String, serde_json::Value> {
+    fn schema(gen: &mut Generator) -> Schema {
+        gen.map_schema::<String, serde_json::Value>()
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        Names {
+            short: "Map",
+            long: "serde_json::Map",
+            path_segments: vec!["serde_json", "Map"],
+            nullable: false,
+            type_params: vec![String::names(), serde_json::Value::names()],
+            const_params: vec![],
+        }
+    }
+}
+
+/// Values of this type can't exist, so nothing is ever serialized. The schema
+/// is empty, with `uninhabited` metadata saying as much. The never type (`!`)
+/// isn't stable yet, so it has no impl.
+impl JsonTypedef for std::convert::Infallible {
+    fn schema(_: &mut Generator) -> Schema {
+        Schema::empty().with_metadata("uninhabited", true)
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        Names {
+            short: "Infallible",
+            long: "std::convert::Infallible",
+            path_segments: vec!["std", "convert", "Infallible"],
+            nullable: false,
+            type_params: vec![],
+            const_params: vec![],
+        }
+    }
+}
+
 impl JsonTypedef for std::path::PathBuf {
     fn schema(gen: &mut Generator) -> Schema {
         gen.sub_schema::<std::path::Path>()
@@ -170,6 +652,33 @@ impl<T: JsonTypedef> JsonTypedef for Option<T> {
     }
 }
 
+#[cfg(feature = "either")]
+impl<L: JsonTypedef, R: JsonTypedef> JsonTypedef for either::Either<L, R> {
+    fn schema(gen: &mut Generator) -> Schema {
+        Schema::properties()
+            .optional_property("Left", gen.sub_schema::<L>())
+            .optional_property("Right", gen.sub_schema::<R>())
+            .build()
+            .with_metadata("minProperties", 1)
+            .with_metadata("maxProperties", 1)
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        Names {
+            short: "Either",
+            long: "either::Either",
+            path_segments: vec!["either", "Either"],
+            nullable: false,
+            type_params: vec![L::names(), R::names()],
+            const_params: vec![],
+        }
+    }
+}
+
 macro_rules! impl_array_like {
 	($($in:ty),*) => {
 		$(
@@ -183,42 +692,81 @@ macro_rules! impl_array_like {
                     }
                 }
 
-                fn referenceable() -> bool {
-                    false
+                impl_array_like!(@rest);
+            }
+        )*
+	};
+    // Types that byte sequences are commonly stored in. Those are subject to
+    // `GeneratorBuilder::byte_sequences`.
+	(sequences: $($in:ty),*) => {
+		$(
+            impl<T: JsonTypedef> JsonTypedef for $in {
+                fn schema(gen: &mut Generator) -> Schema {
+                    gen.sequence_schema::<T>()
                 }
 
-                fn names() -> Names {
-                    Names {
-                        short: "array",
-                        long: "array",
-                        nullable: false,
-                        type_params: vec![T::names()],
-                        const_params: vec![],
-                    }
-                }
+                impl_array_like!(@rest);
             }
         )*
 	};
+	(@rest) => {
+        fn referenceable() -> bool {
+            false
+        }
+
+        fn names() -> Names {
+            Names {
+                short: "array",
+                long: "array",
+                path_segments: vec!["array"],
+                nullable: false,
+                type_params: vec![T::names()],
+                const_params: vec![],
+            }
+        }
+	};
 }
 
+impl_array_like!(sequences: Vec<T>, [T]);
+
 impl_array_like!(
-    Vec<T>,
     VecDeque<T>,
     std::collections::BTreeSet<T>,
     BinaryHeap<T>,
     HashSet<T>,
-    LinkedList<T>,
-    [T]
+    LinkedList<T>
 );
 
+#[cfg(feature = "indexmap")]
+impl_array_like!(indexmap::IndexSet<T>);
+
+#[cfg(feature = "bytes")]
+macro_rules! impl_bytes {
+	($($in:ty),*) => {
+		$(
+            impl JsonTypedef for $in {
+                fn schema(gen: &mut Generator) -> Schema {
+                    gen.sequence_schema::<u8>()
+                }
+
+                fn referenceable() -> bool {
+                    false
+                }
+
+                fn names() -> Names {
+                    Vec::<u8>::names()
+                }
+            }
+        )*
+	};
+}
+
+#[cfg(feature = "bytes")]
+impl_bytes!(bytes::Bytes, bytes::BytesMut);
+
 impl<T: JsonTypedef, const N: usize> JsonTypedef for [T; N] {
     fn schema(gen: &mut Generator) -> Schema {
-        Schema {
-            ty: SchemaType::Elements {
-                elements: Box::new(gen.sub_schema::<T>()),
-            },
-            ..Schema::default()
-        }
+        gen.sequence_schema::<T>()
     }
 
     fn referenceable() -> bool {
@@ -229,6 +777,7 @@ impl<T: JsonTypedef, const N: usize> JsonTypedef for [T; N] {
         Names {
             short: "array",
             long: "array",
+            path_segments: vec!["array"],
             nullable: false,
             type_params: vec![T::names()],
             const_params: vec![],
@@ -236,17 +785,66 @@ impl<T: JsonTypedef, const N: usize> JsonTypedef for [T; N] {
     }
 }
 
-macro_rules! impl_map_like {
-	($($in:ty),*) => {
+/// Serialized as a sequence of at most `CAP` elements, even for `u8`.
+#[cfg(feature = "arrayvec")]
+impl<T: JsonTypedef, const CAP: usize> JsonTypedef for arrayvec::ArrayVec<T, CAP> {
+    fn schema(gen: &mut Generator) -> Schema {
+        Schema::elements(gen.sub_schema::<T>()).with_metadata("maxItems", CAP)
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        Vec::<T>::names()
+    }
+}
+
+/// `CAP` is in bytes, so `maxLength` is an upper bound on the number of
+/// characters rather than the exact limit.
+#[cfg(feature = "arrayvec")]
+impl<const CAP: usize> JsonTypedef for arrayvec::ArrayString<CAP> {
+    fn schema(_: &mut Generator) -> Schema {
+        Schema::of_type(TypeSchema::String).with_metadata("maxLength", CAP)
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        str::names()
+    }
+}
+
+/// Tuples serialize as arrays of fixed length. _Typedef_ arrays are
+/// homogenous, so unless all elements have the same schema, the elements
+/// schema is empty and the schema of each element is listed in a
+/// `tupleElements` metadata entry instead. Either way, the length is in
+/// `minItems` and `maxItems` entries.
+fn tuple_schema(elements: Vec<Schema>) -> Schema {
+    let len = elements.len();
+    let schema = if elements.windows(2).all(|pair| pair[0] == pair[1]) {
+        Schema::elements(elements[0].clone())
+    } else {
+        Schema::elements(Schema::empty()).with_metadata(
+            "tupleElements",
+            serde_json::to_value(elements).expect("schemas are always serializable"),
+        )
+    };
+
+    schema
+        .with_metadata("minItems", len)
+        .with_metadata("maxItems", len)
+}
+
+macro_rules! impl_tuples {
+	($(($($name:ident),+)),*) => {
 		$(
-            impl<K: ToString, V: JsonTypedef> JsonTypedef for $in {
+            impl<$($name: JsonTypedef),+> JsonTypedef for ($($name,)+) {
                 fn schema(gen: &mut Generator) -> Schema {
-                    Schema {
-                        ty: SchemaType::Values {
-                            values: Box::new(gen.sub_schema::<V>()),
-                        },
-                        ..Schema::default()
-                    }
+                    tuple_schema(vec![$(gen.sub_schema::<$name>()),+])
                 }
 
                 fn referenceable() -> bool {
@@ -255,10 +853,11 @@ macro_rules! impl_map_like {
 
                 fn names() -> Names {
                     Names {
-                        short: "map",
-                        long: "map",
+                        short: "tuple",
+                        long: "tuple",
+                        path_segments: vec!["tuple"],
                         nullable: false,
-                        type_params: vec![V::names()],
+                        type_params: vec![$($name::names()),+],
                         const_params: vec![],
                     }
                 }
@@ -267,14 +866,27 @@ macro_rules! impl_map_like {
 	};
 }
 
-impl_map_like!(BTreeMap<K, V>, HashMap<K, V>);
+impl_tuples!(
+    (T0),
+    (T0, T1),
+    (T0, T1, T2),
+    (T0, T1, T2, T3),
+    (T0, T1, T2, T3, T4),
+    (T0, T1, T2, T3, T4, T5),
+    (T0, T1, T2, T3, T4, T5, T6),
+    (T0, T1, T2, T3, T4, T5, T6, T7),
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8),
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9),
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10),
+    (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11)
+);
 
-macro_rules! impl_transparent {
+macro_rules! impl_map_like {
 	($($in:ty),*) => {
 		$(
-            impl<T: JsonTypedef> JsonTypedef for $in {
+            impl<K: JsonTypedefKey, V: JsonTypedef> JsonTypedef for $in {
                 fn schema(gen: &mut Generator) -> Schema {
-                    gen.sub_schema::<T>()
+                    gen.map_schema::<K, V>()
                 }
 
                 fn referenceable() -> bool {
@@ -282,23 +894,67 @@ macro_rules! impl_transparent {
                 }
 
                 fn names() -> Names {
-                    T::names()
+                    Names {
+                        short: "map",
+                        long: "map",
+                        path_segments: vec!["map"],
+                        nullable: false,
+                        type_params: vec![V::names()],
+                        const_params: vec![],
+                    }
                 }
             }
         )*
 	};
 }
 
+impl_map_like!(BTreeMap<K, V>, HashMap<K, V>);
+
+#[cfg(feature = "indexmap")]
+impl_map_like!(indexmap::IndexMap<K, V>);
+
+macro_rules! impl_transparent {
+	($($in:ty),*) => {
+		$(
+            impl<T: JsonTypedef> JsonTypedef for $in {
+                impl_transparent!(@body);
+            }
+        )*
+	};
+    // Pointers that can hold `str` or `[T]`
+	(unsized: $($in:ty),*) => {
+		$(
+            impl<T: JsonTypedef + ?Sized> JsonTypedef for $in {
+                impl_transparent!(@body);
+            }
+        )*
+	};
+	(@body) => {
+        fn schema(gen: &mut Generator) -> Schema {
+            gen.sub_schema::<T>()
+        }
+
+        fn referenceable() -> bool {
+            false
+        }
+
+        fn names() -> Names {
+            T::names()
+        }
+	};
+}
+
 impl_transparent!(
     std::num::Wrapping<T>,
     Cell<T>,
     RefCell<T>,
-    Box<T>,
     Mutex<T>,
     RwLock<T>,
     Reverse<T>
 );
 
+impl_transparent!(unsized: Box<T>, std::rc::Rc<T>, std::sync::Arc<T>);
+
 macro_rules! impl_transparent_lifetime {
 	($($in:ty),*) => {
 		$(
@@ -321,6 +977,29 @@ macro_rules! impl_transparent_lifetime {
 
 impl_transparent_lifetime!(&'a T, &'a mut T);
 
+// `serde` serializes weak pointers as `None` once the value is gone
+macro_rules! impl_weak {
+	($($in:ty),*) => {
+		$(
+            impl<T: JsonTypedef> JsonTypedef for $in {
+                fn schema(gen: &mut Generator) -> Schema {
+                    gen.sub_schema::<Option<T>>()
+                }
+
+                fn referenceable() -> bool {
+                    false
+                }
+
+                fn names() -> Names {
+                    Option::<T>::names()
+                }
+            }
+        )*
+	};
+}
+
+impl_weak!(std::rc::Weak<T>, std::sync::Weak<T>);
+
 impl<'a, T: JsonTypedef + Clone> JsonTypedef for Cow<'a, T> {
     fn schema(gen: &mut Generator) -> Schema {
         gen.sub_schema::<T>()
@@ -353,6 +1032,7 @@ impl<'a> JsonTypedef for Arguments<'a> {
         Names {
             short: "string",
             long: "string",
+            path_segments: vec!["string"],
             nullable: false,
             type_params: vec![],
             const_params: vec![],
@@ -383,6 +1063,7 @@ macro_rules! impl_range {
                     Names {
                         short: stringify!($in),
                         long: concat!("std::ops::", stringify!($in)),
+                        path_segments: vec!["std", "ops", stringify!($in)],
                         nullable: false,
                         type_params: vec![T::names()],
                         const_params: vec![],
@@ -1,20 +1,36 @@
+use crate::{JsonTypedef, Names};
+
 /// Provide a unique [`TypeId`] for the given concrete type.
 ///
 /// The ID should reliably identify a type across [`type_id`] calls within one
 /// run of a binary, but not across runs. This means it's not suitable for exporting
 /// in public APIs, but can be useful internally. `jtd-derive` uses it during schema
 /// generation to detect name collisions of schema definitions.
-///
-/// Inspiration: [`GREsau/schemars#178`](https://github.com/GREsau/schemars/pull/178)
-pub(crate) fn type_id<T: ?Sized>() -> TypeId {
-    TypeId(type_id::<T> as usize)
+pub(crate) fn type_id<T: JsonTypedef + ?Sized>() -> TypeId {
+    TypeId::new(std::any::type_name::<T>(), T::names())
 }
 
 /// An ID uniquely identifying a concrete type.
 ///
-/// The ID should reliably identify a type across [`type_id`] calls within one
-/// run of a binary, but not across runs. This means it's not suitable for exporting
-/// in public APIs, but can be useful internally. `jtd-derive` uses it during schema
-/// generation to detect name collisions of schema definitions.
-#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
-pub(crate) struct TypeId(usize);
+/// We can't use [`std::any::TypeId`] since it requires `'static` types. Instead,
+/// this combines the type name provided by the compiler with the [`Names`] the
+/// type reports. Neither is guaranteed to be unique on its own, but two distinct
+/// types would have to clash on both to be mistaken for one another.
+///
+/// An earlier version used the address of a generic function instead, but
+/// identical code folding can merge those for distinct types, and they're
+/// not guaranteed to be the same for one type either.
+///
+/// This is `pub` only so that it can appear in the hidden
+/// `JsonTypedefDyn::dyn_type_id` method. It can't be named outside the crate.
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct TypeId {
+    type_name: &'static str,
+    names: Names,
+}
+
+impl TypeId {
+    pub(crate) fn new(type_name: &'static str, names: Names) -> Self {
+        Self { type_name, names }
+    }
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "arrayvec")]
+
+use arrayvec::{ArrayString, ArrayVec};
+use jtd_derive::{Generator, JsonTypedef};
+
+#[test]
+fn capacity_limits() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        scores: ArrayVec<u32, 4>,
+        code: ArrayString<16>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Foo>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "scores": { "elements": { "type": "uint32" }, "metadata": { "maxItems": 4 } },
+                "code": { "type": "string", "metadata": { "maxLength": 16 } },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "bytes")]
+
+use bytes::{Bytes, BytesMut};
+use jtd_derive::{ByteSequences, Generator, JsonTypedef};
+
+#[derive(JsonTypedef)]
+#[allow(unused)]
+struct Packet {
+    payload: Bytes,
+    buffer: BytesMut,
+}
+
+#[test]
+fn elements() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Packet>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "payload": { "elements": { "type": "uint8" } },
+                "buffer": { "elements": { "type": "uint8" } },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn base64() {
+    let schema = Generator::builder()
+        .byte_sequences(ByteSequences::Base64)
+        .build()
+        .into_root_schema::<Packet>()
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "payload": { "type": "string", "metadata": { "contentEncoding": "base64" } },
+                "buffer": { "type": "string", "metadata": { "contentEncoding": "base64" } },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "cli")]
+
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// Write a file into a temporary directory shared by these tests. Names have
+/// to be unique across tests, since they run in parallel.
+fn temp_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("jtd-derive-cli-test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn run(args: &[&dyn AsRef<OsStr>]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_jtd-derive"))
+        .args(args.iter().map(|arg| arg.as_ref()))
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+#[test]
+fn validate_rejects_unresolved_refs() {
+    let schema = temp_file(
+        "schema.json",
+        r#"{ "definitions": { "a": { "type": "uint8" } }, "elements": { "ref": "b" } }"#,
+    );
+    let instance = temp_file("instance.json", "[]");
+
+    let output = run(&[&"validate", &schema, &instance]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).ends_with("schema.json: refs to missing definitions: b\n"));
+}
+
+#[test]
+fn validate() {
+    let schema = temp_file("validate.json", r#"{ "elements": { "type": "uint8" } }"#);
+    let valid = temp_file("validate_valid.json", "[1, 2]");
+    let invalid = temp_file("validate_invalid.json", r#"[1, "2"]"#);
+
+    let output = run(&[&"validate", &schema, &valid]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).ends_with("validate_valid.json: ok\n"));
+
+    let output = run(&[&"validate", &schema, &invalid]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).ends_with("validate_invalid.json: /1 rejected by /elements/type\n"));
+}
+
+#[test]
+fn diff_identical() {
+    let schema = r#"{ "properties": { "a": { "type": "uint8" } } }"#;
+    let old = temp_file("diff_identical_old.json", schema);
+    let new = temp_file("diff_identical_new.json", schema);
+
+    let output = run(&[&"diff", &old, &new]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn diff_changes() {
+    let old = temp_file(
+        "diff_changes_old.json",
+        r#"{ "properties": { "a": { "type": "uint8" }, "b": { "type": "string" } } }"#,
+    );
+    let new = temp_file(
+        "diff_changes_new.json",
+        r#"{ "properties": { "a": { "type": "uint16" }, "c": { "type": "string" } } }"#,
+    );
+
+    let output = run(&[&"diff", &old, &new]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "~ /properties/a/type: \"uint8\" -> \"uint16\"\n\
+         - /properties/b: {\"type\":\"string\"}\n\
+         + /properties/c: {\"type\":\"string\"}\n"
+    );
+}
+
+#[test]
+fn diff_escapes_paths() {
+    let old = temp_file(
+        "diff_escapes_old.json",
+        r#"{ "properties": { "a/b": { "type": "uint8" }, "c~d": { "type": "string" } } }"#,
+    );
+    let new = temp_file(
+        "diff_escapes_new.json",
+        r#"{ "properties": { "a/b": { "type": "uint16" } } }"#,
+    );
+
+    let output = run(&[&"diff", &old, &new]);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "~ /properties/a~1b/type: \"uint8\" -> \"uint16\"\n\
+         - /properties/c~0d: {\"type\":\"string\"}\n"
+    );
+}
+
+#[test]
+fn json_schema() {
+    let schema = temp_file(
+        "json_schema.json",
+        r#"{ "elements": { "type": "string" } }"#,
+    );
+
+    let output = run(&[&"json-schema", &schema]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(stdout(&output)).unwrap(),
+        serde_json::json! {{
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "array",
+            "items": { "type": "string" },
+        }}
+    );
+}
+
+#[test]
+fn fmt() {
+    let schema = temp_file("fmt.json", r#"{"elements":{"type":"string"}}"#);
+
+    let output = run(&[&"fmt", &schema]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "{\n  \"elements\": {\n    \"type\": \"string\"\n  }\n}\n"
+    );
+}
+
+#[test]
+fn usage_errors() {
+    for output in [run(&[]), run(&[&"diff"]), run(&[&"frobnicate"])] {
+        assert_eq!(output.status.code(), Some(2));
+        assert_eq!(stdout(&output), "");
+        assert!(stderr(&output).starts_with("error: invalid arguments\n\nUsage: jtd-derive"));
+    }
+}
@@ -0,0 +1,18 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[typedef(rename_all = "lowercase")]
+enum Foo {
+    FooBar,
+    Foobar,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type", rename_all = "UPPERCASE")]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+enum Bar {
+    Baz { x: u32 },
+    BAZ { y: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+enum Foo {
+    #[typedef(catch_all)]
+    Bar,
+    #[typedef(catch_all)]
+    Baz,
+}
+
+fn main() {}
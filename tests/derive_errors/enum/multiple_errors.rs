@@ -0,0 +1,20 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[typedef(repr = "uint8")]
+#[typedef(tag = "type")]
+enum Foo {
+    Bar {
+        #[typedef(since = "v1")]
+        x: u32,
+    },
+    Baz {
+        #[typedef(unknown)]
+        y: u32,
+    },
+    Qux {
+        z: u32,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[typedef(repr = "uint64")]
+enum Foo {
+    A,
+    B,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type", repr = "uint8")]
+enum Bar {
+    A { x: u32 },
+}
+
+fn main() {}
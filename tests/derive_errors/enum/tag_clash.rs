@@ -0,0 +1,27 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type")]
+enum Foo {
+    Bar {
+        r#type: String,
+    },
+    Baz {
+        #[typedef(rename = "type")]
+        kind: String,
+    },
+}
+
+// fine, these never show up next to the tag
+#[derive(JsonTypedef)]
+#[typedef(tag = "type")]
+enum Ok {
+    Bar {
+        #[typedef(skip)]
+        r#type: String,
+        #[typedef(rename = "Type")]
+        kind: String,
+    },
+}
+
+fn main() {}
@@ -1,11 +1,12 @@
 #[derive(jtd_derive::JsonTypedef)]
+#[typedef(tag = "type")]
 enum Tuple {
-    Foo(u32),
+    Foo(u32, u32),
     Bar(),
 }
 
 #[derive(jtd_derive::JsonTypedef)]
-enum MixedWithTuple {
+enum NewtypeWithoutTag {
     Foo { x: String },
     Bar(u32),
 }
@@ -0,0 +1,9 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[typedef(values = "u32")]
+enum Foo {
+    Bar,
+}
+
+fn main() {}
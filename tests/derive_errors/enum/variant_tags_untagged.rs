@@ -0,0 +1,10 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[typedef(variant_tags)]
+enum Foo {
+    Bar,
+    Baz,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[typedef(discriminants)]
+struct Foo {
+    x: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+struct Foo {
+    #[typedef(rename = "b")]
+    a: u32,
+    b: u32,
+}
+
+fn main() {}
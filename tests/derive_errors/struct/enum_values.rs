@@ -0,0 +1,17 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+struct Foo {
+    #[typedef(enum_values())]
+    a: String,
+    #[typedef(enum_values("x", "y", "x"))]
+    b: String,
+    #[typedef(enum_values("x", 1))]
+    c: String,
+    #[typedef(enum_values = "x")]
+    d: String,
+    #[typedef(enum_values("x"), as = "String")]
+    e: String,
+}
+
+fn main() {}
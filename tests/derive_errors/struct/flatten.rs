@@ -0,0 +1,38 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef, serde::Deserialize)]
+struct Inner {
+    x: u32,
+}
+
+#[derive(JsonTypedef)]
+struct SchemaWith {
+    #[typedef(flatten, schema_with = "schema")]
+    inner: Inner,
+}
+
+#[derive(JsonTypedef, serde::Deserialize)]
+struct Metadata {
+    #[serde(flatten)]
+    #[typedef(metadata(x = "stuff"))]
+    inner: Inner,
+}
+
+#[derive(JsonTypedef)]
+struct Doc {
+    /// Some docs.
+    #[typedef(flatten)]
+    inner: Inner,
+}
+
+#[derive(JsonTypedef)]
+struct Constraints {
+    #[typedef(flatten, min_properties = 1)]
+    inner: Inner,
+}
+
+fn schema(_gen: &mut jtd_derive::Generator) -> jtd_derive::schema::Schema {
+    jtd_derive::schema::Schema::of_type(jtd_derive::schema::TypeSchema::Uint32)
+}
+
+fn main() {}
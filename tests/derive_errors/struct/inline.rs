@@ -0,0 +1,17 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+struct Foo {
+    #[typedef(inline = true)]
+    x: u32,
+    #[typedef(inline, schema_with = "schema")]
+    y: u32,
+    #[typedef(ref, inline)]
+    z: u32,
+}
+
+fn schema(_gen: &mut jtd_derive::Generator) -> jtd_derive::schema::Schema {
+    jtd_derive::schema::Schema::of_type(jtd_derive::schema::TypeSchema::Uint32)
+}
+
+fn main() {}
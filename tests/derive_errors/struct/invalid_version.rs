@@ -0,0 +1,9 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+struct Foo {
+    #[typedef(since = "v1")]
+    x: u32,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[typedef(key)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(key)]
+struct Wrapper(Vec<u32>);
+
+fn main() {}
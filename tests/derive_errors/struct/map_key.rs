@@ -0,0 +1,16 @@
+use std::collections::HashMap;
+
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(JsonTypedef)]
+struct Foo {
+    by_point: HashMap<Point, u32>,
+}
+
+fn main() {}
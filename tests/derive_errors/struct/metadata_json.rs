@@ -0,0 +1,15 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[typedef(metadata_json = "[1, 2]")]
+struct Foo {
+    x: u32,
+}
+
+#[derive(JsonTypedef)]
+struct Bar {
+    #[typedef(metadata_json = "{ \"x\": }")]
+    x: u32,
+}
+
+fn main() {}
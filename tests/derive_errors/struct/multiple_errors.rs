@@ -0,0 +1,21 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+struct Foo<T> {
+    #[typedef(nullable = true)]
+    x: u32,
+    #[typedef(since = "v1")]
+    y: T,
+    #[typedef(enum_values())]
+    z: String,
+}
+
+#[derive(JsonTypedef)]
+struct Bar<T> {
+    #[typedef(unknown)]
+    x: T,
+    #[typedef(inline = true)]
+    y: u32,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use jtd_derive::schema::Schema;
+use jtd_derive::{Generator, JsonTypedef};
+
+fn custom(_gen: &mut Generator) -> Schema {
+    Schema::default()
+}
+
+#[derive(JsonTypedef)]
+struct Foo {
+    #[typedef(as = "String", schema_with = "custom")]
+    x: u32,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use jtd_derive::JsonTypedef;
+
+mod as_string {
+    pub fn serialize<S: serde::Serializer>(x: &u32, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(x)
+    }
+}
+
+#[derive(JsonTypedef, serde::Serialize)]
+struct Foo {
+    #[serde(serialize_with = "as_string::serialize")]
+    x: u32,
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef, serde::Serialize, Clone)]
+#[typedef(strict)]
+#[serde(into = "String", rename_all = "camelCase")]
+struct Foo {
+    #[serde(rename = "y", alias = "w")]
+    x: u32,
+    #[serde(skip_serializing, serialize_with = "ser")]
+    #[typedef(as = "String")]
+    z: u32,
+}
+
+impl From<Foo> for String {
+    fn from(_: Foo) -> String {
+        String::new()
+    }
+}
+
+fn ser<S: serde::Serializer>(_: &u32, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_unit()
+}
+
+#[derive(JsonTypedef, serde::Serialize)]
+#[typedef(strict)]
+enum Bar {
+    #[serde(skip_serializing)]
+    Baz,
+}
+
+fn main() {}
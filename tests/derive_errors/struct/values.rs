@@ -0,0 +1,9 @@
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[typedef(values = "u32", transparent)]
+struct Foo {
+    x: u32,
+}
+
+fn main() {}
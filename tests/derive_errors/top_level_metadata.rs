@@ -1,5 +1,5 @@
 #[derive(jtd_derive::JsonTypedef)]
-#[typedef(metadata(foo, bar, foo = 2))]
+#[typedef(metadata(foo, bar, foo = b"2"))]
 struct Foo {
     bar: u32,
 }
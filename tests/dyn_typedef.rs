@@ -0,0 +1,154 @@
+use jtd_derive::schema::{Schema, SchemaType, TypeSchema};
+use jtd_derive::{Generator, JsonTypedef, JsonTypedefDyn, Names, TypeOf};
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Foo {
+    x: u32,
+}
+
+/// A provider whose schema is only known at runtime.
+struct Plugin {
+    name: &'static str,
+    ty: TypeSchema,
+}
+
+impl JsonTypedefDyn for Plugin {
+    fn dyn_schema(&self, _: &mut Generator) -> Schema {
+        Schema {
+            ty: SchemaType::Type {
+                r#type: self.ty.clone(),
+            },
+            ..Schema::default()
+        }
+    }
+
+    fn dyn_names(&self) -> Names {
+        Names {
+            short: self.name,
+            long: self.name,
+            path_segments: vec![self.name],
+            nullable: false,
+            type_params: vec![],
+            const_params: vec![],
+        }
+    }
+}
+
+#[test]
+fn same_schema_as_static() {
+    let providers: [Box<dyn JsonTypedefDyn>; 2] =
+        [Box::new(Foo { x: 5 }), Box::new(TypeOf::<Foo>::new())];
+
+    for provider in providers {
+        assert_eq!(
+            Generator::default()
+                .into_root_schema_dyn(provider.as_ref())
+                .unwrap(),
+            Generator::default().into_root_schema::<Foo>().unwrap()
+        );
+    }
+}
+
+#[test]
+fn manual_impl() {
+    let plugin = Plugin {
+        name: "plugin",
+        ty: TypeSchema::String,
+    };
+
+    let mut gen = Generator::default();
+    let schema = gen.sub_schema_dyn(&plugin);
+
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{ "ref": "plugin" }}
+    );
+
+    let root = Generator::builder()
+        .top_level_ref()
+        .build()
+        .into_root_schema_dyn(&plugin)
+        .unwrap();
+
+    assert_eq!(
+        serde_json::to_value(root).unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "plugin": { "type": "string" },
+            },
+            "ref": "plugin",
+        }}
+    );
+}
+
+struct Plugins(Vec<Plugin>);
+
+impl JsonTypedefDyn for Plugins {
+    fn dyn_schema(&self, gen: &mut Generator) -> Schema {
+        Schema {
+            ty: SchemaType::Elements {
+                elements: Box::new(Schema {
+                    ty: SchemaType::Properties {
+                        properties: self
+                            .0
+                            .iter()
+                            .map(|p| (p.name, gen.sub_schema_dyn(p)))
+                            .collect(),
+                        optional_properties: Default::default(),
+                        additional_properties: false,
+                    },
+                    ..Schema::default()
+                }),
+            },
+            ..Schema::default()
+        }
+    }
+
+    fn dyn_referenceable(&self) -> bool {
+        false
+    }
+
+    fn dyn_names(&self) -> Names {
+        Names {
+            short: "Plugins",
+            long: "dyn_typedef::Plugins",
+            path_segments: vec!["dyn_typedef", "Plugins"],
+            nullable: false,
+            type_params: vec![],
+            const_params: vec![],
+        }
+    }
+}
+
+#[test]
+fn same_type_different_names() {
+    let plugins = Plugins(vec![
+        Plugin {
+            name: "a",
+            ty: TypeSchema::String,
+        },
+        Plugin {
+            name: "b",
+            ty: TypeSchema::Boolean,
+        },
+    ]);
+
+    let root = Generator::default().into_root_schema_dyn(&plugins).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(root).unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "a": { "type": "string" },
+                "b": { "type": "boolean" },
+            },
+            "elements": {
+                "properties": {
+                    "a": { "ref": "a" },
+                    "b": { "ref": "b" },
+                },
+            },
+        }}
+    );
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "either")]
+
+use either::Either;
+use jtd_derive::{Generator, JsonTypedef};
+
+#[test]
+fn either() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        id: Either<u32, String>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Foo>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "id": {
+                    "optionalProperties": {
+                        "Left": { "type": "uint32" },
+                        "Right": { "type": "string" },
+                    },
+                    "metadata": { "minProperties": 1, "maxProperties": 1 }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
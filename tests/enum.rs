@@ -1,4 +1,4 @@
-use jtd_derive::{Generator, JsonTypedef};
+use jtd_derive::{GenError, Generator, JsonTypedef};
 
 #[derive(JsonTypedef)]
 #[allow(dead_code)]
@@ -84,3 +84,260 @@ fn enum_struct_variants() {
         }}
     );
 }
+
+#[derive(JsonTypedef)]
+#[typedef(discriminants, rename_all = "lowercase")]
+#[allow(dead_code)]
+#[repr(u64)]
+enum WithDiscriminants {
+    Foo = 3,
+    Bar,
+    Baz = u64::MAX,
+}
+
+#[test]
+fn enum_discriminants() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<WithDiscriminants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "metadata": {
+                "discriminants": { "foo": 3, "bar": 4, "baz": u64::MAX },
+            },
+            "enum": ["foo", "bar", "baz"],
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type", rename_all = "snake_case")]
+#[allow(dead_code)]
+enum WithCatchAll {
+    Known {
+        x: u32,
+    },
+    #[typedef(catch_all)]
+    Unknown {},
+}
+
+#[test]
+fn enum_catch_all() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<WithCatchAll>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "metadata": { "catchAll": "unknown" },
+            "discriminator": "type",
+            "mapping": {
+                "known": {
+                    "properties": { "x": { "type": "uint32" } },
+                    "additionalProperties": true,
+                },
+                "unknown": {
+                    "additionalProperties": true,
+                },
+            },
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "event", rename_all = "kebab-case", variant_tags)]
+#[allow(dead_code)]
+enum WithVariantTags {
+    UserCreated { id: u32 },
+    UserDeleted { id: u32 },
+}
+
+#[test]
+fn enum_variant_tags() {
+    assert_eq!(WithVariantTags::DISCRIMINATOR, "event");
+    assert_eq!(
+        WithVariantTags::variant_tags(),
+        ["user-created", "user-deleted"]
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Payload {
+    y: String,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type")]
+#[allow(dead_code)]
+enum NewtypeVariants {
+    Bar { x: u32 },
+    Baz(Payload),
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type")]
+#[allow(dead_code)]
+enum PrimitiveNewtypeVariant {
+    Bar(u32),
+}
+
+#[test]
+fn enum_newtype_variants() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<NewtypeVariants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "Bar": {
+                    "properties": {
+                        "x": {"type": "uint32"}
+                    },
+                    "additionalProperties": true
+                },
+                "Baz": {
+                    "properties": {
+                        "y": {"type": "string"}
+                    },
+                    "additionalProperties": true
+                }
+            }
+        }}
+    );
+
+    assert_eq!(
+        Generator::default()
+            .into_root_schema::<PrimitiveNewtypeVariant>()
+            .unwrap_err(),
+        GenError::InvalidFlatten {
+            type_name: "uint32".to_string()
+        }
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(repr = "uint8")]
+#[allow(dead_code)]
+enum WithRepr {
+    Low = 1,
+    Mid = 5,
+    High = 10,
+}
+
+#[test]
+fn enum_repr() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<WithRepr>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "metadata": {
+                "allowedValues": [1, 5, 10],
+                "discriminants": { "Low": 1, "Mid": 5, "High": 10 }
+            },
+            "type": "uint8"
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+enum ExplicitDiscriminants {
+    Foo = 3,
+    Bar,
+    Baz = 10,
+}
+
+#[test]
+fn enum_explicit_discriminants() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<ExplicitDiscriminants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "metadata": {
+                "discriminants": { "Foo": 3, "Bar": 4, "Baz": 10 }
+            },
+            "enum": ["Foo", "Bar", "Baz"]
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type")]
+#[allow(dead_code)]
+enum MixedStrictness {
+    Loose {
+        x: u32,
+    },
+    #[typedef(deny_unknown_fields)]
+    Strict {
+        x: u32,
+    },
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type", deny_unknown_fields)]
+#[allow(dead_code)]
+enum MostlyStrict {
+    Strict {
+        x: u32,
+    },
+    #[typedef(allow_unknown_fields)]
+    Loose {
+        x: u32,
+    },
+}
+
+#[test]
+fn variant_unknown_fields() {
+    let strict = serde_json::json! {{
+        "properties": { "x": { "type": "uint32" } },
+    }};
+    let loose = serde_json::json! {{
+        "properties": { "x": { "type": "uint32" } },
+        "additionalProperties": true,
+    }};
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<MixedStrictness>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "Loose": loose,
+                "Strict": strict,
+            },
+        }}
+    );
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<MostlyStrict>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "Loose": loose,
+                "Strict": strict,
+            },
+        }}
+    );
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "garde")]
+
+use jtd_derive::{Generator, JsonTypedef};
+
+const MAX: u32 = 100;
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Foo {
+    #[garde(range(min = 1, max = MAX))]
+    x: u32,
+    #[garde(ascii, length(chars, min = 1, max = 10), pattern(r"^\w+$"))]
+    name: String,
+    #[garde(email)]
+    email: Option<String>,
+    #[garde(ipv4)]
+    address: String,
+    #[garde(length(min = 1), inner(length(max = 5)))]
+    tags: Vec<String>,
+    #[garde(skip)]
+    other: bool,
+}
+
+#[test]
+fn constraints_become_metadata() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Foo>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": {
+                    "metadata": { "minimum": 1, "maximum": 100 },
+                    "type": "uint32",
+                },
+                "name": {
+                    "metadata": { "minLength": 1, "maxLength": 10, "pattern": r"^\w+$" },
+                    "type": "string",
+                },
+                "email": {
+                    "metadata": { "format": "email" },
+                    "type": "string",
+                    "nullable": true,
+                },
+                "address": {
+                    "metadata": { "format": "ipv4" },
+                    "type": "string",
+                },
+                "tags": {
+                    "metadata": { "minItems": 1 },
+                    "elements": { "type": "string" },
+                },
+                "other": { "type": "boolean" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
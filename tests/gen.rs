@@ -1,4 +1,6 @@
-use jtd_derive::{GenError, Generator, JsonTypedef};
+use jtd_derive::{
+    ByteSequences, GenError, Generator, Integers128, Integers64, JsonTypedef, PropertyCase,
+};
 
 #[derive(JsonTypedef)]
 #[allow(dead_code)]
@@ -27,9 +29,906 @@ fn name_collisions() {
         .naming_short()
         .build()
         .into_root_schema::<Wrapping>()
-        .unwrap_err();
+        .unwrap_err()
+    else {
+        panic!("expected a name collision")
+    };
 
     assert_eq!(id, "Foo");
     assert!([type1.as_str(), type2.as_str()].contains(&"gen::Foo"));
     assert!([type1.as_str(), type2.as_str()].contains(&"gen::foo::Foo"));
 }
+
+#[derive(JsonTypedef)]
+#[allow(dead_code, non_snake_case)]
+struct Cased {
+    snake_case: u8,
+    camelCase: Vec<Tagged>,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "kind_of")]
+#[allow(dead_code, non_camel_case_types)]
+enum Tagged {
+    Variant_A { inner_field: bool },
+}
+
+#[test]
+fn property_case() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .prefer_inline()
+                .property_case(PropertyCase::Camel)
+                .build()
+                .into_root_schema::<Cased>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "snakeCase": { "type": "uint8" },
+                "camelCase": {
+                    "elements": {
+                        "discriminator": "kindOf",
+                        "mapping": {
+                            "Variant_A": {
+                                "properties": { "innerField": { "type": "boolean" } },
+                                "additionalProperties": true,
+                            },
+                        },
+                    },
+                },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code, non_snake_case)]
+struct Colliding {
+    foo_bar: u8,
+    fooBar: u8,
+}
+
+#[test]
+fn property_case_collision() {
+    assert_eq!(
+        Generator::builder()
+            .property_case(PropertyCase::Snake)
+            .build()
+            .into_root_schema::<Colliding>()
+            .unwrap_err(),
+        GenError::PropertyCollision {
+            property1: "fooBar".to_string(),
+            property2: "foo_bar".to_string(),
+            cased: "foo_bar".to_string(),
+        }
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Deep {
+    x: Vec<Option<Foo>>,
+}
+
+#[test]
+fn depth_limit() {
+    let gen = || Generator::builder().prefer_inline().max_depth(3).build();
+
+    assert_eq!(
+        gen().into_root_schema::<Deep>().unwrap_err(),
+        GenError::DepthLimitExceeded {
+            limit: 3,
+            chain: ["gen::Deep", "array<gen::Foo>", "gen::Foo", "gen::Foo"]
+                .map(String::from)
+                .into()
+        }
+    );
+    assert!(gen().into_root_schema::<Vec<Option<Foo>>>().is_ok());
+}
+
+#[test]
+fn path_segments() {
+    assert_eq!(foo::Foo::names().path_segments, ["gen", "foo", "Foo"]);
+    assert_eq!(
+        std::net::IpAddr::names().path_segments,
+        ["std", "net", "IpAddr"]
+    );
+    assert_eq!(u32::names().path_segments, ["uint32"]);
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Generic<T> {
+    x: T,
+    y: Option<Box<Generic<T>>>,
+}
+
+#[test]
+fn naming_context() {
+    let root = Generator::builder()
+        .naming_custom_with_context(|ctx| {
+            assert!(!ctx.is_root);
+            let params: Vec<_> = ctx.names.type_params.iter().map(|p| p.short).collect();
+            format!("{}_{}", ctx.names.short, params.join("_"))
+        })
+        .build()
+        .into_root_schema::<Vec<Generic<u8>>>()
+        .unwrap();
+
+    assert_eq!(
+        root.definitions.keys().collect::<Vec<_>>(),
+        ["Generic_uint8"]
+    );
+
+    let root = Generator::builder()
+        .naming_custom_with_context(|ctx| match ctx.is_root {
+            true => "Root".to_string(),
+            false => ctx.names.to_string(),
+        })
+        .build()
+        .into_root_schema::<Generic<u8>>()
+        .unwrap();
+
+    assert_eq!(root.definitions.keys().collect::<Vec<_>>(), ["Root"]);
+}
+
+#[test]
+fn fallible_naming() {
+    let err = Generator::builder()
+        .naming_custom(|names| match names.short {
+            "Foo" => Err(GenError::invalid_name(names, "reserved")),
+            short => Ok(short.to_string()),
+        })
+        .build()
+        .into_root_schema::<Wrapping>()
+        .unwrap_err();
+
+    assert!(matches!(err, GenError::InvalidName { reason, .. } if reason == "reserved"));
+}
+
+#[test]
+fn byte_sequences() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo<'a> {
+        vec: Vec<u8>,
+        array: [u8; 4],
+        slice: &'a [u8],
+        other: Vec<u16>,
+        set: std::collections::BTreeSet<u8>,
+    }
+
+    let base64 = serde_json::json!({
+        "type": "string",
+        "metadata": { "contentEncoding": "base64" }
+    });
+
+    let schema = Generator::builder()
+        .byte_sequences(ByteSequences::Base64)
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "vec": base64,
+                "array": base64,
+                "slice": base64,
+                "other": { "elements": { "type": "uint16" } },
+                "set": { "elements": { "type": "uint8" } },
+            },
+            "additionalProperties": true
+        }}
+    );
+
+    let schema = Generator::default().into_root_schema::<Foo>().unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap()["properties"]["vec"],
+        serde_json::json!({ "elements": { "type": "uint8" } })
+    );
+}
+
+#[test]
+fn integers_64() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        unsigned: u64,
+        signed: Option<i64>,
+        len: Vec<usize>,
+    }
+
+    let err = Generator::default().into_root_schema::<Foo>().unwrap_err();
+    assert_eq!(
+        err,
+        GenError::UnsupportedInteger {
+            type_name: "u64".to_string(),
+            bits: 64,
+            chain: vec!["gen::Foo".to_string(), "u64".to_string()]
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "`u64` has no Typedef equivalent (in gen::Foo -> u64); \
+         pick a representation with `GeneratorBuilder::integers_64`"
+    );
+
+    let schema = Generator::builder()
+        .integers_64(Integers64::Int32)
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "unsigned": { "type": "uint32", "metadata": { "format": "uint64" } },
+                "signed": {
+                    "type": "int32",
+                    "metadata": { "format": "int64" },
+                    "nullable": true
+                },
+                "len": {
+                    "elements": { "type": "uint32", "metadata": { "format": "uint64" } }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+
+    let schema = Generator::builder()
+        .integers_64(Integers64::String)
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap()["properties"]["signed"],
+        serde_json::json!({
+            "type": "string",
+            "metadata": { "format": "int64" },
+            "nullable": true
+        })
+    );
+}
+
+#[test]
+fn integers_128() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        unsigned: u128,
+        signed: Vec<i128>,
+    }
+
+    let err = Generator::builder()
+        .integers_64(Integers64::String)
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap_err();
+    assert_eq!(
+        err,
+        GenError::UnsupportedInteger {
+            type_name: "u128".to_string(),
+            bits: 128,
+            chain: vec!["gen::Foo".to_string(), "u128".to_string()]
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "`u128` has no Typedef equivalent (in gen::Foo -> u128); \
+         pick a representation with `GeneratorBuilder::integers_128`"
+    );
+
+    let schema = Generator::builder()
+        .integers_128(Integers128::String)
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "unsigned": { "type": "string", "metadata": { "format": "uint128" } },
+                "signed": {
+                    "elements": { "type": "string", "metadata": { "format": "int128" } }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+
+    let schema = Generator::builder()
+        .integers_128(Integers128::Float64)
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap()["properties"]["unsigned"],
+        serde_json::json!({ "type": "float64", "metadata": { "format": "uint128" } })
+    );
+}
+
+#[test]
+fn non_zero_wide_integers() {
+    use std::num::{NonZeroI128, NonZeroU64, NonZeroUsize};
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        id: NonZeroU64,
+        len: NonZeroUsize,
+        big: NonZeroI128,
+    }
+
+    let err = Generator::default().into_root_schema::<Foo>().unwrap_err();
+    assert_eq!(
+        err,
+        GenError::UnsupportedInteger {
+            type_name: "NonZeroU64".to_string(),
+            bits: 64,
+            chain: vec!["gen::Foo".to_string(), "std::num::NonZeroU64".to_string()]
+        }
+    );
+
+    let schema = Generator::builder()
+        .integers_64(Integers64::Int32)
+        .integers_128(Integers128::String)
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "std::num::NonZeroU64": { "type": "uint32", "metadata": { "format": "uint64" } },
+                "std::num::NonZeroUsize": { "type": "uint32", "metadata": { "format": "uint64" } },
+                "std::num::NonZeroI128": { "type": "string", "metadata": { "format": "int128" } },
+            },
+            "properties": {
+                "id": { "ref": "std::num::NonZeroU64" },
+                "len": { "ref": "std::num::NonZeroUsize" },
+                "big": { "ref": "std::num::NonZeroI128" },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn system_time() {
+    use std::time::SystemTime;
+
+    let schema = Generator::builder()
+        .integers_64(Integers64::String)
+        .build()
+        .into_root_schema::<SystemTime>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "secs_since_epoch": { "type": "string", "metadata": { "format": "uint64" } },
+                "nanos_since_epoch": { "type": "uint32" },
+            },
+        }}
+    );
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Event {
+        at: SystemTime,
+    }
+
+    let err = Generator::default()
+        .into_root_schema::<Event>()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`u64` has no Typedef equivalent (in gen::Event -> std::time::SystemTime -> u64); \
+         pick a representation with `GeneratorBuilder::integers_64`"
+    );
+
+    // the same keys serde uses
+    let value = serde_json::to_value(SystemTime::UNIX_EPOCH).unwrap();
+    let mut keys: Vec<_> = value.as_object().unwrap().keys().collect();
+    keys.sort();
+    assert_eq!(keys, ["nanos_since_epoch", "secs_since_epoch"]);
+}
+
+#[test]
+fn freeform_json() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        any: serde_json::Value,
+        maybe: Option<serde_json::Value>,
+        object: serde_json::Map<String, serde_json::Value>,
+    }
+
+    let schema = Generator::default().into_root_schema::<Foo>().unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "any": {},
+                "maybe": { "nullable": true },
+                "object": { "values": {} },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn infallible() {
+    use std::convert::Infallible;
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Response<E> {
+        value: u32,
+        error: Option<E>,
+    }
+
+    let schema = Generator::default()
+        .into_root_schema::<Response<Infallible>>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "value": { "type": "uint32" },
+                "error": { "metadata": { "uninhabited": true }, "nullable": true },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn tuples() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        point: (f64, f64),
+        pair: (u32, Option<String>),
+    }
+
+    let schema = Generator::default().into_root_schema::<Foo>().unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "point": {
+                    "elements": { "type": "float64" },
+                    "metadata": { "minItems": 2, "maxItems": 2 }
+                },
+                "pair": {
+                    "elements": {},
+                    "metadata": {
+                        "minItems": 2,
+                        "maxItems": 2,
+                        "tupleElements": [
+                            { "type": "uint32" },
+                            { "type": "string", "nullable": true },
+                        ]
+                    }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+    assert_eq!(
+        <(u32, String)>::names().to_string(),
+        "tuple<uint32, string>"
+    );
+}
+
+#[test]
+fn shared_pointers() {
+    use std::rc::{Rc, Weak};
+    use std::sync::Arc;
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Node {
+        name: Arc<String>,
+        parent: Weak<Node>,
+        children: Vec<Rc<Node>>,
+    }
+
+    let schema = Generator::default().into_root_schema::<Node>().unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "gen::Node": {
+                    "properties": {
+                        "name": { "type": "string" },
+                        "parent": { "ref": "gen::Node", "nullable": true },
+                        "children": { "elements": { "ref": "gen::Node" } },
+                    },
+                    "additionalProperties": true
+                }
+            },
+            "ref": "gen::Node"
+        }}
+    );
+}
+
+#[test]
+fn unsized_pointers() {
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Interned {
+        name: Box<str>,
+        alias: Rc<str>,
+        label: Arc<str>,
+        tags: Box<[String]>,
+        scores: Arc<[u32]>,
+    }
+
+    let schema = Generator::default().into_root_schema::<Interned>().unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "name": { "type": "string" },
+                "alias": { "type": "string" },
+                "label": { "type": "string" },
+                "tags": { "elements": { "type": "string" } },
+                "scores": { "elements": { "type": "uint32" } },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn char_length() {
+    let schema = Generator::default().into_root_schema::<char>().unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json!({
+            "type": "string",
+            "metadata": { "minLength": 1, "maxLength": 1 }
+        })
+    );
+
+    let schema = Generator::builder()
+        .omit_char_length()
+        .build()
+        .into_root_schema::<char>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json!({ "type": "string" })
+    );
+}
+
+#[test]
+fn map_key_schema() {
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(JsonTypedef, PartialEq, Eq, PartialOrd, Ord)]
+    #[allow(unused)]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        by_name: HashMap<String, u8>,
+        by_color: BTreeMap<Color, u8>,
+        by_id: HashMap<u32, u8>,
+    }
+
+    let schema = Generator::builder()
+        .prefer_inline()
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "by_name": { "values": { "type": "uint8" } },
+                "by_color": {
+                    "values": { "type": "uint8" },
+                    "metadata": { "keySchema": { "enum": ["Red", "Green"] } }
+                },
+                "by_id": {
+                    "values": { "type": "uint8" },
+                    "metadata": { "keySchema": { "type": "uint32" } }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+
+    let Err(GenError::NonStringKey { key_type }) = Generator::builder()
+        .deny_non_string_keys()
+        .build()
+        .into_root_schema::<Foo>()
+    else {
+        panic!("expected a NonStringKey error");
+    };
+    assert_eq!(key_type, "uint32");
+}
+
+#[test]
+fn wide_integer_and_bool_keys() {
+    use std::collections::HashMap;
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        by_id: HashMap<u64, u8>,
+        by_flag: HashMap<bool, u8>,
+    }
+
+    let schema = Generator::default().into_root_schema::<Foo>().unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "by_id": {
+                    "values": { "type": "uint8" },
+                    "metadata": {
+                        "keySchema": { "type": "string", "metadata": { "format": "uint64" } }
+                    }
+                },
+                "by_flag": {
+                    "values": { "type": "uint8" },
+                    "metadata": { "keySchema": { "type": "boolean" } }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn enum_keys_as_properties() {
+    use std::collections::BTreeMap;
+
+    #[derive(JsonTypedef, PartialEq, Eq, PartialOrd, Ord)]
+    #[allow(unused)]
+    enum Side {
+        Left,
+        Right,
+    }
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        sides: BTreeMap<Side, String>,
+        ids: BTreeMap<u32, String>,
+    }
+
+    let schema = Generator::builder()
+        .enum_keys_as_properties()
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "sides": {
+                    "optionalProperties": {
+                        "Left": { "type": "string" },
+                        "Right": { "type": "string" },
+                    },
+                },
+                "ids": {
+                    "values": { "type": "string" },
+                    "metadata": { "keySchema": { "type": "uint32" } }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn newtype_map_keys() {
+    use std::collections::HashMap;
+
+    #[derive(JsonTypedef, PartialEq, Eq, Hash)]
+    #[typedef(key, metadata(format = "uuid"))]
+    struct UserId(String);
+
+    #[derive(JsonTypedef, PartialEq, Eq, Hash)]
+    #[typedef(key)]
+    struct Name(String);
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        users: HashMap<UserId, u8>,
+        names: HashMap<Name, u8>,
+    }
+
+    let schema = Generator::default().into_root_schema::<Foo>().unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "users": {
+                    "values": { "type": "uint8" },
+                    "metadata": {
+                        "keySchema": {
+                            "type": "string",
+                            "metadata": { "format": "uuid" }
+                        }
+                    }
+                },
+                "names": { "values": { "type": "uint8" } },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn source_locations() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Located {
+        x: u32,
+    }
+    let line = line!() - 3;
+
+    let schema = Generator::builder()
+        .top_level_ref()
+        .source_locations()
+        .build()
+        .into_root_schema::<Located>()
+        .unwrap();
+    let location = &serde_json::to_value(schema).unwrap()["definitions"]["gen::Located"]
+        ["metadata"]["sourceLocation"];
+    assert_eq!(location, &format!("tests/gen.rs:{}", line));
+
+    let schema = Generator::builder()
+        .top_level_ref()
+        .build()
+        .into_root_schema::<Located>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap()["definitions"]["gen::Located"]["metadata"],
+        serde_json::Value::Null
+    );
+}
+
+#[test]
+fn versions() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        always: u32,
+        #[typedef(since = "1.2")]
+        added: u32,
+        #[typedef(until = "2")]
+        removed: u32,
+        #[typedef(since = "1.2.1", until = "1.3")]
+        short_lived: u32,
+    }
+
+    let fields = |version: Option<&str>| {
+        let mut builder = Generator::builder();
+        if let Some(version) = version {
+            builder.for_version(version);
+        }
+        let schema =
+            serde_json::to_value(builder.build().into_root_schema::<Foo>().unwrap()).unwrap();
+        let mut fields: Vec<_> = schema["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        fields.sort();
+        fields
+    };
+
+    assert_eq!(fields(None), ["added", "always", "removed", "short_lived"]);
+    assert_eq!(fields(Some("1.0")), ["always", "removed"]);
+    assert_eq!(fields(Some("1.2")), ["added", "always", "removed"]);
+    assert_eq!(
+        fields(Some("1.2.5")),
+        ["added", "always", "removed", "short_lived"]
+    );
+    assert_eq!(fields(Some("2.0.0")), ["added", "always"]);
+
+    let schema = Generator::default().into_root_schema::<Foo>().unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap()["properties"]["short_lived"]["metadata"],
+        serde_json::json!({ "versions": { "since": "1.2.1", "until": "1.3" } })
+    );
+
+    let Err(GenError::InvalidVersion { version }) = Generator::builder()
+        .for_version("1.x")
+        .build()
+        .into_root_schema::<Foo>()
+    else {
+        panic!("expected an InvalidVersion error");
+    };
+    assert_eq!(version, "1.x");
+}
+
+#[test]
+fn from_config() {
+    use jtd_derive::{GeneratorConfig, SchemaMode};
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        payload: Vec<u8>,
+        #[typedef(since = "2")]
+        added: u32,
+    }
+
+    let config: GeneratorConfig = serde_json::from_value(serde_json::json!({
+        "inlining": "always",
+        "byte_sequences": "base64",
+        "mode": "serialize",
+        "for_version": "1.0",
+        "metadata_namespace": "x-jtd-derive",
+    }))
+    .unwrap();
+    assert_eq!(config.mode, SchemaMode::Serialize);
+
+    let schema = Generator::from_config(&config)
+        .into_root_schema::<Foo>()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "payload": {
+                    "type": "string",
+                    "metadata": {
+                        "x-jtd-derive": { "contentEncoding": "base64" }
+                    }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+
+    assert!(
+        serde_json::from_value::<GeneratorConfig>(serde_json::json!({
+            "inlinig": "always",
+        }))
+        .is_err()
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct ConstParams<const N: usize, const B: bool, const C: char, const I: i16> {
+    x: [u8; N],
+}
+
+#[test]
+fn const_param_names() {
+    assert_eq!(
+        ConstParams::<3, true, ',', -5>::names().const_params,
+        ["3", "true", "','", "-5"]
+    );
+    assert_eq!(
+        format!("{:#}", ConstParams::<3, false, '>', 0>::names()),
+        "ConstParams<3, false, '>', 0>"
+    );
+}
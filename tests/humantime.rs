@@ -0,0 +1,68 @@
+#![cfg(feature = "humantime")]
+
+use std::time::{Duration, SystemTime};
+
+use humantime_serde::Serde;
+use jtd_derive::{Generator, JsonTypedef};
+
+#[test]
+fn durations_and_timestamps() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Config {
+        timeout: Serde<Duration>,
+        retry_after: Option<Serde<Duration>>,
+        not_before: Serde<SystemTime>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Config>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "timeout": {
+                    "type": "string",
+                    "metadata": { "format": "humantime-duration" }
+                },
+                "retry_after": {
+                    "type": "string",
+                    "nullable": true,
+                    "metadata": { "format": "humantime-duration" }
+                },
+                "not_before": {
+                    "type": "string",
+                    "metadata": { "format": "date-time" }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn serde_with_humantime() {
+    #[derive(JsonTypedef, serde::Serialize)]
+    #[allow(unused)]
+    struct Config {
+        #[serde(with = "humantime_serde")]
+        timeout: Duration,
+        #[serde(with = "humantime_serde")]
+        not_before: SystemTime,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Config>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "timeout": {
+                    "type": "string",
+                    "metadata": { "format": "humantime-duration" }
+                },
+                "not_before": {
+                    "type": "string",
+                    "metadata": { "format": "date-time" }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "indexmap")]
+
+use indexmap::{IndexMap, IndexSet};
+use jtd_derive::{Generator, JsonTypedef};
+
+#[test]
+fn index_maps_and_sets() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        map: IndexMap<String, u32>,
+        set: IndexSet<bool>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Foo>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "map": { "values": { "type": "uint32" } },
+                "set": { "elements": { "type": "boolean" } },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
@@ -92,3 +92,84 @@ fn prefer_ref() {
         }}
     );
 }
+
+#[derive(JsonTypedef)]
+#[allow(unused)]
+struct InlineField {
+    #[typedef(inline)]
+    inlined: Option<Bar>,
+    #[typedef(inline)]
+    no_effect: Vec<u32>,
+    by_ref: Bar,
+}
+
+#[test]
+fn inline_field() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<InlineField>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "inlining::Bar": {
+                    "properties": { "bar": { "type": "uint32" } },
+                    "additionalProperties": true,
+                },
+            },
+            "properties": {
+                "inlined": {
+                    "properties": { "bar": { "type": "uint32" } },
+                    "additionalProperties": true,
+                    "nullable": true,
+                },
+                "no_effect": { "elements": { "type": "uint32" } },
+                "by_ref": { "ref": "inlining::Bar" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(unused)]
+struct RefField {
+    inlined: Bar,
+    #[typedef(ref)]
+    by_ref: Option<Bar>,
+    #[typedef(ref)]
+    no_effect: Vec<u32>,
+}
+
+#[test]
+fn ref_field() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .prefer_inline()
+                .build()
+                .into_root_schema::<RefField>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "inlining::Bar": {
+                    "properties": { "bar": { "type": "uint32" } },
+                    "additionalProperties": true,
+                },
+            },
+            "properties": {
+                "by_ref": { "ref": "inlining::Bar", "nullable": true },
+                "no_effect": { "elements": { "type": "uint32" } },
+                "inlined": {
+                    "properties": { "bar": { "type": "uint32" } },
+                    "additionalProperties": true,
+                },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "inventory")]
+
+use jtd_derive::registry::{all_types, SchemaRegistry};
+use jtd_derive::JsonTypedef;
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Foo {
+    bar: Bar,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+enum Bar {
+    A,
+    B,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Generic<T> {
+    x: T,
+}
+
+jtd_derive::register_type!(Generic<u8>);
+
+#[test]
+fn derived_types_are_collected() {
+    let mut names: Vec<_> = all_types().map(|ty| ty.names().long).collect();
+    names.sort();
+
+    assert_eq!(
+        names,
+        ["inventory::Bar", "inventory::Foo", "inventory::Generic"]
+    );
+}
+
+#[test]
+fn register_all() {
+    let mut registry = SchemaRegistry::new();
+    registry.register_all().unwrap();
+
+    assert_eq!(
+        registry.names().collect::<Vec<_>>(),
+        [
+            "inventory::Bar",
+            "inventory::Foo",
+            "inventory::Generic<uint8>"
+        ]
+    );
+    assert_eq!(
+        registry.get("inventory::Bar"),
+        Some(
+            &jtd_derive::Generator::default()
+                .into_root_schema::<Bar>()
+                .unwrap()
+        )
+    );
+}
@@ -0,0 +1,213 @@
+use jtd_derive::{Generator, JsonTypedef};
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Foo {
+    bar: Bar,
+    baz: Option<Vec<u8>>,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type", deny_unknown_fields)]
+#[allow(dead_code)]
+enum Bar {
+    Baz { x: Option<Kind> },
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+enum Kind {
+    A,
+    B,
+}
+
+#[test]
+fn conversion() {
+    assert_eq!(
+        Generator::builder()
+            .naming_short()
+            .build()
+            .into_root_schema::<Foo>()
+            .unwrap()
+            .to_json_schema(),
+        serde_json::json! {{
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "definitions": {
+                "Bar": {
+                    "type": "object",
+                    "oneOf": [{
+                        "type": "object",
+                        "properties": {
+                            "type": { "const": "Baz" },
+                            "x": {
+                                "anyOf": [
+                                    { "type": "null" },
+                                    { "$ref": "#/definitions/Kind" },
+                                ],
+                            },
+                        },
+                        "required": ["type", "x"],
+                        "additionalProperties": false,
+                    }],
+                },
+                "Kind": {
+                    "type": "string",
+                    "enum": ["A", "B"],
+                },
+            },
+            "type": "object",
+            "properties": {
+                "bar": { "$ref": "#/definitions/Bar" },
+                "baz": {
+                    "type": ["array", "null"],
+                    "items": { "type": "integer", "minimum": 0, "maximum": 255 },
+                },
+            },
+            "required": ["bar", "baz"],
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[test]
+fn nullable_type_and_metadata() {
+    assert_eq!(
+        jtd_derive::json_schema::from_jtd(&serde_json::json! {{
+            "metadata": { "description": "hello", "type": "ignored" },
+            "enum": ["A", "B"],
+            "nullable": true,
+        }}),
+        serde_json::json! {{
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "description": "hello",
+            "type": ["string", "null"],
+            "enum": ["A", "B", null],
+        }}
+    );
+}
+
+/// Whether `null` passes the keywords the conversion can emit.
+fn admits_null(schema: &serde_json::Value) -> bool {
+    use serde_json::Value;
+
+    let type_ok = match schema.get("type") {
+        Some(Value::String(ty)) => ty == "null",
+        Some(Value::Array(types)) => types.contains(&"null".into()),
+        _ => true,
+    };
+    let enum_ok = match schema.get("enum") {
+        Some(Value::Array(values)) => values.contains(&Value::Null),
+        _ => true,
+    };
+    let any_of_ok = match schema.get("anyOf") {
+        Some(Value::Array(schemas)) => schemas.iter().any(admits_null),
+        _ => true,
+    };
+    let one_of_ok = match schema.get("oneOf") {
+        Some(Value::Array(schemas)) => schemas.iter().filter(|s| admits_null(s)).count() == 1,
+        _ => true,
+    };
+
+    type_ok && enum_ok && any_of_ok && one_of_ok
+}
+
+#[test]
+fn nullable_discriminator() {
+    let schema = Generator::builder()
+        .prefer_inline()
+        .build()
+        .into_root_schema::<Option<Bar>>()
+        .unwrap()
+        .to_json_schema();
+
+    assert!(admits_null(&schema));
+    assert_eq!(schema["anyOf"][0], serde_json::json!({ "type": "null" }));
+    assert_eq!(schema["anyOf"][1]["type"], "object");
+    assert!(!admits_null(&schema["anyOf"][1]));
+
+    // the non-null forms still accept `null` through their `type`
+    let schema = Generator::default()
+        .into_root_schema::<Option<Vec<u8>>>()
+        .unwrap()
+        .to_json_schema();
+    assert!(admits_null(&schema));
+    assert_eq!(schema["type"], serde_json::json!(["array", "null"]));
+}
+
+#[test]
+fn ref_escaping() {
+    assert_eq!(
+        jtd_derive::json_schema::from_jtd(&serde_json::json! {{
+            "ref": "a::Foo<b/c, 5>",
+        }}),
+        serde_json::json! {{
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "$ref": "#/definitions/a::Foo%3Cb~1c,%205%3E",
+        }}
+    );
+}
+
+#[test]
+fn tuples() {
+    let root = Generator::default()
+        .into_root_schema::<(u32, String)>()
+        .unwrap();
+    assert_eq!(
+        root.to_json_schema(),
+        serde_json::json! {{
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "array",
+            "items": [{ "type": "integer", "minimum": 0, "maximum": 4294967295u32 }, { "type": "string" }],
+            "minItems": 2,
+            "maxItems": 2,
+        }}
+    );
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn schemars_interop() {
+    let root = Generator::builder()
+        .naming_short()
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+    let schemars = root.to_schemars();
+
+    assert_eq!(
+        schemars.meta_schema.as_deref(),
+        Some(jtd_derive::json_schema::DIALECT)
+    );
+    assert_eq!(
+        schemars.definitions.keys().collect::<Vec<_>>(),
+        ["Bar", "Kind"]
+    );
+    // schemars stores numeric bounds as floats, so compare what it parsed
+    // rather than the re-serialized JSON
+    let object = schemars.schema.object.unwrap();
+    assert_eq!(object.properties.keys().collect::<Vec<_>>(), ["bar", "baz"]);
+    assert_eq!(object.additional_properties.as_deref(), Some(&true.into()));
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn schemars_nullable_discriminator() {
+    use schemars::schema::{InstanceType, Schema, SingleOrVec};
+
+    let schemars = Generator::builder()
+        .prefer_inline()
+        .build()
+        .into_root_schema::<Option<Bar>>()
+        .unwrap()
+        .to_schemars();
+
+    assert_eq!(schemars.schema.instance_type, None);
+    let any_of = schemars.schema.subschemas.unwrap().any_of.unwrap();
+    let Schema::Object(null) = &any_of[0] else {
+        panic!("expected a schema object");
+    };
+    assert_eq!(
+        null.instance_type,
+        Some(SingleOrVec::Single(Box::new(InstanceType::Null)))
+    );
+}
@@ -0,0 +1,39 @@
+#![cfg(all(feature = "log", feature = "tracing"))]
+
+use jtd_derive::{Generator, JsonTypedef};
+
+#[test]
+fn log_and_tracing_levels() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Config {
+        log: log::LevelFilter,
+        tracing: tracing_core::Level,
+    }
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .naming_short()
+                .build()
+                .into_root_schema::<Config>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "Level": {
+                    "enum": ["TRACE", "DEBUG", "INFO", "WARN", "ERROR"]
+                },
+                "LevelFilter": {
+                    "enum": ["OFF", "ERROR", "WARN", "INFO", "DEBUG", "TRACE"]
+                },
+            },
+            "properties": {
+                "log": { "ref": "LevelFilter" },
+                "tracing": { "ref": "Level" },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
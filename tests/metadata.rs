@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
+
 use jtd_derive::{Generator, JsonTypedef};
 
 #[test]
 fn top_level() {
     #[derive(JsonTypedef)]
-    #[typedef(metadata(x = "\"stuff\"", y = "{ \"inner\": 5 }"))]
+    #[typedef(metadata(x = "stuff"), metadata_json = r#"{ "y": { "inner": 5 } }"#)]
     #[allow(unused)]
     struct Foo {
         bar: u32,
@@ -26,12 +28,51 @@ fn top_level() {
     );
 }
 
+#[test]
+fn typed_literals() {
+    #[derive(JsonTypedef)]
+    #[typedef(metadata(
+        x = "stuff",
+        n = 5,
+        f = 1.5,
+        flag = true,
+        version = "1.0",
+        truthy = "true",
+        nothing = "null",
+        y = "{ \"inner\": 5 }"
+    ))]
+    #[allow(unused)]
+    struct Foo {
+        bar: u32,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Foo>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "bar": { "type": "uint32" },
+            },
+            "additionalProperties": true,
+            "metadata": {
+                "x": "stuff",
+                "n": 5,
+                "f": 1.5,
+                "flag": true,
+                "version": "1.0",
+                "truthy": "true",
+                "nothing": "null",
+                "y": "{ \"inner\": 5 }"
+            }
+        }}
+    );
+}
+
 #[test]
 fn struct_field() {
     #[derive(JsonTypedef)]
     #[allow(unused)]
     struct Foo {
-        #[typedef(metadata(x = "\"stuff\"", y = "{ \"inner\": 5 }"))]
+        #[typedef(metadata(x = "stuff"), metadata_json = r#"{ "y": { "inner": 5 } }"#)]
         bar: u32,
     }
 
@@ -61,7 +102,7 @@ fn variant_field() {
     #[allow(unused)]
     enum Foo {
         Bar {
-            #[typedef(metadata(x = "\"stuff\"", y = "{ \"inner\": 5 }"))]
+            #[typedef(metadata(x = "stuff"), metadata_json = r#"{ "y": { "inner": 5 } }"#)]
             baz: u32,
         },
     }
@@ -95,13 +136,16 @@ fn overwriting() {
     #[derive(JsonTypedef)]
     #[allow(unused)]
     struct Foo {
-        #[typedef(metadata(x = "\"outer\"", y = "{ \"stuff\": 6 }"))]
+        #[typedef(metadata(x = "outer"), metadata_json = r#"{ "y": { "stuff": 6 } }"#)]
         bar: Bar,
     }
 
     #[derive(JsonTypedef)]
     #[allow(unused)]
-    #[typedef(metadata(x = "\"inner\"", z = "{ \"morestuff\": 3 }"))]
+    #[typedef(
+        metadata(x = "inner"),
+        metadata_json = r#"{ "z": { "morestuff": 3 } }"#
+    )]
     struct Bar {
         x: u32,
     }
@@ -139,3 +183,469 @@ fn overwriting() {
         }}
     );
 }
+
+#[test]
+fn field_metadata_merge() {
+    #[derive(JsonTypedef)]
+    #[typedef(metadata(description = "a bar"))]
+    #[allow(unused)]
+    struct Bar {
+        x: u32,
+    }
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        #[typedef(metadata(description = "overridden"))]
+        overriding: Bar,
+        #[typedef(
+            metadata(description = "ignored", extra = true),
+            metadata_merge = "preserve"
+        )]
+        preserving: Bar,
+    }
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .prefer_inline()
+                .build()
+                .into_root_schema::<Foo>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "overriding": {
+                    "properties": {
+                        "x": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                    "metadata": { "description": "overridden" }
+                },
+                "preserving": {
+                    "properties": {
+                        "x": { "type": "uint32" },
+                    },
+                    "additionalProperties": true,
+                    "metadata": { "description": "a bar", "extra": true }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn fuzz_hint_helpers() {
+    let schema = jtd_derive::schema::Schema::empty().with_fuzz_hint("en_us.lorem.word");
+    assert_eq!(schema.metadata.fuzz_hint(), Some("en_us.lorem.word"));
+}
+
+#[test]
+fn fuzz_hints() {
+    #[derive(JsonTypedef)]
+    #[typedef(fuzz_hint = "en_us.internet.email")]
+    #[allow(unused)]
+    struct Email(String);
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct User {
+        #[typedef(fuzz_hint = "en_us.name.name")]
+        name: String,
+        email: Email,
+    }
+
+    let schema = Generator::builder()
+        .prefer_inline()
+        .build()
+        .into_root_schema::<User>()
+        .unwrap();
+    assert_eq!(schema.schema.metadata.fuzz_hint(), None);
+    assert_eq!(
+        serde_json::to_value(schema).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "name": {
+                    "type": "string",
+                    "metadata": { "fuzzHint": "en_us.name.name" }
+                },
+                "email": {
+                    "type": "string",
+                    "metadata": { "fuzzHint": "en_us.internet.email" }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn namespace() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Inner {
+        x: u32,
+    }
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        #[typedef(metadata(description = "an initial"), fuzz_hint = "en_us.lorem.word")]
+        initial: char,
+        #[typedef(since = "1.1")]
+        inner: Inner,
+    }
+
+    let schema = Generator::builder()
+        .metadata_namespace("x-jtd-derive")
+        .source_locations()
+        .build()
+        .into_root_schema::<Foo>()
+        .unwrap();
+    let schema = serde_json::to_value(schema).unwrap();
+
+    assert_eq!(
+        schema["properties"]["initial"]["metadata"],
+        serde_json::json!({
+            "description": "an initial",
+            "fuzzHint": "en_us.lorem.word",
+            "x-jtd-derive": { "minLength": 1, "maxLength": 1 }
+        })
+    );
+    assert_eq!(
+        schema["properties"]["inner"]["metadata"],
+        serde_json::json!({
+            "x-jtd-derive": { "versions": { "since": "1.1" } }
+        })
+    );
+    assert!(
+        schema["definitions"]["metadata::Inner"]["metadata"]["x-jtd-derive"]["sourceLocation"]
+            .is_string()
+    );
+}
+
+/// A documented type.
+///
+/// With a second paragraph.
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Documented {
+    /// A documented field.
+    x: u32,
+    y: Option<DocumentedVariants>,
+}
+
+/// The kind of thing.
+#[derive(JsonTypedef)]
+#[typedef(tag = "type")]
+#[allow(dead_code)]
+enum DocumentedVariants {
+    Foo {
+        /// The foo.
+        foo: u32,
+    },
+}
+
+#[test]
+fn descriptions() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .descriptions()
+                .build()
+                .into_root_schema::<Documented>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "metadata::DocumentedVariants": {
+                    "metadata": {
+                        "description": "The kind of thing."
+                    },
+                    "discriminator": "type",
+                    "mapping": {
+                        "Foo": {
+                            "properties": {
+                                "foo": {
+                                    "metadata": { "description": "The foo." },
+                                    "type": "uint32"
+                                }
+                            },
+                            "additionalProperties": true
+                        }
+                    }
+                }
+            },
+            "metadata": {
+                "description": "A documented type.\n\nWith a second paragraph."
+            },
+            "properties": {
+                "x": {
+                    "metadata": { "description": "A documented field." },
+                    "type": "uint32"
+                },
+                "y": {
+                    "ref": "metadata::DocumentedVariants",
+                    "nullable": true
+                }
+            },
+            "additionalProperties": true
+        }}
+    );
+
+    // doc comments are left out by default
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<Documented>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "metadata::DocumentedVariants": {
+                    "discriminator": "type",
+                    "mapping": {
+                        "Foo": {
+                            "properties": {
+                                "foo": { "type": "uint32" }
+                            },
+                            "additionalProperties": true
+                        }
+                    }
+                }
+            },
+            "properties": {
+                "x": { "type": "uint32" },
+                "y": {
+                    "ref": "metadata::DocumentedVariants",
+                    "nullable": true
+                }
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+const MAX_LEN: usize = 32;
+
+#[test]
+fn constraints() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        #[typedef(minimum = "-5", maximum = 100, multiple_of = 0.5)]
+        x: f32,
+        #[typedef(min_length = 1, max_length = "MAX_LEN", pattern = "^[a-z]+$")]
+        name: String,
+        #[typedef(min_items = 1, unique_items)]
+        tags: Vec<String>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Foo>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": {
+                    "metadata": { "minimum": -5, "maximum": 100, "multipleOf": 0.5 },
+                    "type": "float32"
+                },
+                "name": {
+                    "metadata": { "minLength": 1, "maxLength": 32, "pattern": "^[a-z]+$" },
+                    "type": "string"
+                },
+                "tags": {
+                    "metadata": { "minItems": 1, "uniqueItems": true },
+                    "elements": { "type": "string" }
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[deprecated = "use `Current` instead"]
+#[allow(dead_code)]
+struct Outdated {
+    #[deprecated]
+    x: u32,
+    #[typedef(deprecated(note = "unused"))]
+    y: u32,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+enum OutdatedUnitVariants {
+    Foo,
+    #[deprecated(since = "0.2.0")]
+    Bar,
+    #[typedef(deprecated)]
+    Baz,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type")]
+#[allow(dead_code)]
+enum OutdatedVariants {
+    Foo {
+        x: u32,
+    },
+    #[deprecated(since = "0.2.0", note = "use `Foo`")]
+    Bar {
+        x: u32,
+    },
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecation() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Outdated>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "metadata": {
+                "deprecated": true,
+                "deprecationNote": "use `Current` instead"
+            },
+            "properties": {
+                "x": {
+                    "metadata": { "deprecated": true },
+                    "type": "uint32"
+                },
+                "y": {
+                    "metadata": {
+                        "deprecated": true,
+                        "deprecationNote": "unused"
+                    },
+                    "type": "uint32"
+                }
+            },
+            "additionalProperties": true
+        }}
+    );
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<OutdatedUnitVariants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "metadata": {
+                "deprecatedVariants": ["Bar", "Baz"]
+            },
+            "enum": ["Foo", "Bar", "Baz"]
+        }}
+    );
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<OutdatedVariants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "Foo": {
+                    "properties": {
+                        "x": { "type": "uint32" }
+                    },
+                    "additionalProperties": true
+                },
+                "Bar": {
+                    "metadata": {
+                        "deprecated": true,
+                        "deprecationNote": "use `Foo`"
+                    },
+                    "properties": {
+                        "x": { "type": "uint32" }
+                    },
+                    "additionalProperties": true
+                }
+            }
+        }}
+    );
+}
+
+fn build_info() -> BTreeMap<&'static str, serde_json::Value> {
+    BTreeMap::from([("commit", serde_json::json!(env!("CARGO_PKG_VERSION")))])
+}
+
+fn units() -> BTreeMap<&'static str, serde_json::Value> {
+    BTreeMap::from([("units", serde_json::json!(["m", "km"]))])
+}
+
+#[derive(JsonTypedef)]
+#[typedef(metadata_with = "build_info", metadata(stable = true))]
+#[allow(dead_code)]
+struct DynamicMetadata {
+    #[typedef(metadata_with = "units")]
+    distance: u32,
+}
+
+#[test]
+fn metadata_with() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<DynamicMetadata>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "metadata": {
+                "commit": env!("CARGO_PKG_VERSION"),
+                "stable": true
+            },
+            "properties": {
+                "distance": {
+                    "metadata": { "units": ["m", "km"] },
+                    "type": "uint32"
+                }
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[typedef(
+    metadata_json = r#"{ "owner": { "team": "billing", "oncall": ["a", "b"] }, "stable": false }"#,
+    metadata(stable = true)
+)]
+#[allow(dead_code)]
+struct RawJsonMetadata {
+    #[typedef(metadata_json = r#"{ "example": 42 }"#)]
+    x: u32,
+}
+
+#[test]
+fn metadata_json() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<RawJsonMetadata>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "metadata": {
+                "owner": { "team": "billing", "oncall": ["a", "b"] },
+                "stable": true
+            },
+            "properties": {
+                "x": {
+                    "metadata": { "example": 42 },
+                    "type": "uint32"
+                }
+            },
+            "additionalProperties": true
+        }}
+    );
+}
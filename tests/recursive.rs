@@ -1,4 +1,5 @@
-use jtd_derive::{Generator, JsonTypedef};
+use jtd_derive::schema::Schema;
+use jtd_derive::{GenError, Generator, JsonTypedef, Names};
 
 #[derive(JsonTypedef)]
 #[allow(unused)]
@@ -31,3 +32,129 @@ fn recursive() {
         }}
     );
 }
+
+#[derive(JsonTypedef)]
+#[allow(unused)]
+struct Loop(Box<Loop>);
+
+#[derive(JsonTypedef)]
+#[allow(unused)]
+struct Ping(Box<Pong>);
+
+#[derive(JsonTypedef)]
+#[allow(unused)]
+struct Pong(Option<Box<Ping>>);
+
+#[test]
+fn ref_cycles() {
+    assert_eq!(
+        Generator::default().into_root_schema::<Loop>().unwrap_err(),
+        GenError::UnrepresentableCycle {
+            chain: vec!["recursive::Loop".into(), "recursive::Loop".into()]
+        }
+    );
+    assert_eq!(
+        Generator::default().into_root_schema::<Ping>().unwrap_err(),
+        GenError::UnrepresentableCycle {
+            chain: vec![
+                "recursive::Ping".into(),
+                "recursive::Pong".into(),
+                "recursive::Ping".into()
+            ]
+        }
+    );
+}
+
+struct Inline;
+
+impl JsonTypedef for Inline {
+    fn schema(gen: &mut Generator) -> Schema {
+        Schema::elements(gen.sub_schema::<Inline>())
+    }
+
+    fn referenceable() -> bool {
+        false
+    }
+
+    fn names() -> Names {
+        Names {
+            short: "Inline",
+            long: "recursive::Inline",
+            path_segments: vec!["recursive", "Inline"],
+            nullable: false,
+            type_params: vec![],
+            const_params: vec![],
+        }
+    }
+}
+
+#[test]
+fn inline_cycle() {
+    let err = Generator::default()
+        .into_root_schema::<Vec<Inline>>()
+        .unwrap_err();
+
+    assert_eq!(
+        err,
+        GenError::UnrepresentableCycle {
+            chain: vec!["recursive::Inline".into(), "recursive::Inline".into()]
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "type `recursive::Inline` contains itself in a way Typedef can't express: \
+         recursive::Inline -> recursive::Inline"
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(unused)]
+struct Tree {
+    children: Vec<Tree>,
+}
+
+#[test]
+fn wrappers_recurring_through_refs() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<Vec<Tree>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "recursive::Tree": {
+                    "properties": {
+                        "children": { "elements": { "ref": "recursive::Tree" } }
+                    },
+                    "additionalProperties": true,
+                },
+            },
+            "elements": { "ref": "recursive::Tree" },
+        }}
+    );
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<Option<Box<Recursive>>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "recursive::Recursive": {
+                    "properties": {
+                        "inner": {
+                            "ref": "recursive::Recursive",
+                            "nullable": true,
+                        }
+                    },
+                    "additionalProperties": true,
+                },
+            },
+            "ref": "recursive::Recursive",
+            "nullable": true,
+        }}
+    );
+}
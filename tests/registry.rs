@@ -0,0 +1,138 @@
+use jtd_derive::registry::SchemaRegistry;
+use jtd_derive::{Generator, JsonTypedef};
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Foo {
+    x: u32,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+enum Bar {
+    A,
+    B,
+}
+
+fn registry() -> SchemaRegistry {
+    let mut registry = SchemaRegistry::new();
+    registry
+        .register::<Foo>()
+        .unwrap()
+        .register::<Bar>()
+        .unwrap();
+    registry
+}
+
+#[test]
+fn register() {
+    let registry = registry();
+
+    assert_eq!(
+        registry.names().collect::<Vec<_>>(),
+        ["registry::Bar", "registry::Foo"]
+    );
+    assert_eq!(
+        registry.get("registry::Foo"),
+        Some(&Generator::default().into_root_schema::<Foo>().unwrap())
+    );
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn axum() {
+    use http_body_util::BodyExt as _;
+    use tower::ServiceExt as _;
+
+    let router: axum::Router = registry().into_axum_router();
+    let get = |uri: &str, etag: Option<&str>| {
+        let mut req = axum::http::Request::get(uri);
+        if let Some(etag) = etag {
+            req = req.header("if-none-match", etag);
+        }
+        router
+            .clone()
+            .oneshot(req.body(axum::body::Body::empty()).unwrap())
+    };
+
+    let res = get("/schemas", None).await.unwrap();
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers()["content-type"], "application/json");
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+        serde_json::json!(["registry::Bar", "registry::Foo"])
+    );
+
+    let res = get("/schemas/registry::Bar", None).await.unwrap();
+    assert_eq!(res.status(), 200);
+    assert!(res.headers().contains_key("cache-control"));
+    let etag = res.headers()["etag"].to_str().unwrap().to_string();
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+        serde_json::json!({ "enum": ["A", "B"] })
+    );
+
+    let res = get("/schemas/registry::Bar", Some(&etag)).await.unwrap();
+    assert_eq!(res.status(), 304);
+
+    let res = get("/schemas/registry::Baz", None).await.unwrap();
+    assert_eq!(res.status(), 404);
+}
+
+#[cfg(feature = "actix")]
+#[test]
+fn actix() {
+    use actix_web::test::{call_service, init_service, read_body_json, TestRequest};
+
+    actix_web::rt::System::new().block_on(async {
+        let app = init_service(actix_web::App::new().service(registry().into_actix_scope())).await;
+
+        let res = call_service(&app, TestRequest::get().uri("/schemas").to_request()).await;
+        assert_eq!(res.status(), 200);
+        assert_eq!(
+            res.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+        let body: serde_json::Value = read_body_json(res).await;
+        assert_eq!(body, serde_json::json!(["registry::Bar", "registry::Foo"]));
+
+        let res = call_service(
+            &app,
+            TestRequest::get()
+                .uri("/schemas/registry::Bar")
+                .to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), 200);
+        let etag = res
+            .headers()
+            .get("etag")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body: serde_json::Value = read_body_json(res).await;
+        assert_eq!(body, serde_json::json!({ "enum": ["A", "B"] }));
+
+        let res = call_service(
+            &app,
+            TestRequest::get()
+                .uri("/schemas/registry::Bar")
+                .insert_header(("if-none-match", etag))
+                .to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), 304);
+
+        let res = call_service(
+            &app,
+            TestRequest::get()
+                .uri("/schemas/registry::Baz")
+                .to_request(),
+        )
+        .await;
+        assert_eq!(res.status(), 404);
+    });
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "testing")]
+
+use arbitrary::Arbitrary;
+use jtd_derive::testing::assert_round_trip;
+use jtd_derive::{Generator, JsonTypedef};
+use serde::{Deserialize, Serialize};
+
+#[derive(JsonTypedef, Serialize, Deserialize, Arbitrary)]
+struct Foo {
+    x: u32,
+    y: Option<String>,
+    bar: Vec<Bar>,
+}
+
+#[derive(JsonTypedef, Serialize, Deserialize, Arbitrary)]
+#[serde(tag = "type")]
+enum Bar {
+    Baz { z: bool },
+    Qux { w: i16 },
+}
+
+#[test]
+fn matching_serde() {
+    assert_round_trip::<Foo>(Generator::default(), 256);
+}
+
+// `y` is required on input, but may be missing from the output
+#[derive(JsonTypedef, Serialize, Deserialize, Arbitrary)]
+struct Asymmetric {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    y: Option<String>,
+}
+
+#[test]
+fn checks_serialized_schema() {
+    assert_round_trip::<Asymmetric>(Generator::default(), 256);
+}
+
+// the schema claims `x` is serialized as a number, but it's a string
+#[derive(JsonTypedef, Serialize, Deserialize, Arbitrary)]
+struct Diverging {
+    #[serde(with = "as_string")]
+    #[typedef(as = "u32")]
+    x: u32,
+}
+
+mod as_string {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(x: &u32, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(x)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u32, D::Error> {
+        String::deserialize(d)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[test]
+#[should_panic(expected = "serialized value doesn't match the schema")]
+fn diverging_serde() {
+    assert_round_trip::<Diverging>(Generator::default(), 256);
+}
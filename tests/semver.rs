@@ -0,0 +1,34 @@
+#![cfg(feature = "semver")]
+
+use std::collections::BTreeMap;
+
+use jtd_derive::{Generator, JsonTypedef};
+use semver::{Version, VersionReq};
+
+#[test]
+fn versions() {
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Manifest {
+        version: Version,
+        dependencies: BTreeMap<String, VersionReq>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Manifest>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "semver::Version": { "type": "string", "metadata": { "format": "semver" } },
+                "semver::VersionReq": {
+                    "type": "string",
+                    "metadata": { "format": "semver-requirement" }
+                },
+            },
+            "properties": {
+                "version": { "ref": "semver::Version" },
+                "dependencies": { "values": { "ref": "semver::VersionReq" } },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
@@ -1,4 +1,4 @@
-use jtd_derive::{Generator, JsonTypedef};
+use jtd_derive::{GenError, Generator, JsonTypedef, SchemaMode};
 use serde::Deserialize;
 
 #[derive(JsonTypedef, Deserialize)]
@@ -340,3 +340,608 @@ fn rename_all_enum_struct_variants() {
         }}
     );
 }
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct Asymmetric {
+    always: u32,
+    #[serde(default)]
+    defaulted: u32,
+    #[serde(skip_serializing)]
+    input_only: u32,
+    #[serde(skip_deserializing)]
+    output_only: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maybe: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    list: Vec<u32>,
+}
+
+#[test]
+fn schema_modes() {
+    let schema = |mode| {
+        serde_json::to_value(
+            Generator::builder()
+                .mode(mode)
+                .build()
+                .into_root_schema::<Asymmetric>()
+                .unwrap(),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(
+        schema(SchemaMode::Deserialize),
+        serde_json::json! {{
+            "properties": {
+                "always": { "type": "uint32" },
+                "input_only": { "type": "uint32" },
+                "maybe": { "type": "uint32", "nullable": true },
+            },
+            "optionalProperties": {
+                "defaulted": { "type": "uint32" },
+                "list": { "elements": { "type": "uint32" } },
+            },
+            "additionalProperties": true
+        }}
+    );
+    assert_eq!(
+        schema(SchemaMode::Serialize),
+        serde_json::json! {{
+            "properties": {
+                "always": { "type": "uint32" },
+                "defaulted": { "type": "uint32" },
+                "output_only": { "type": "uint32" },
+            },
+            "optionalProperties": {
+                "maybe": { "type": "uint32", "nullable": true },
+                "list": { "elements": { "type": "uint32" } },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct RenameFields {
+    #[typedef(rename = "type")]
+    type_: String,
+    #[serde(rename = "ID")]
+    id: u32,
+    #[serde(rename = "serde_name")]
+    #[typedef(rename = "typedef_name")]
+    both: bool,
+    foo_bar: bool,
+}
+
+#[test]
+fn rename_fields() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<RenameFields>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "type": { "type": "string" },
+                "ID": { "type": "uint32" },
+                "typedef_name": { "type": "boolean" },
+                "fooBar": { "type": "boolean" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(dead_code)]
+enum RenameVariants {
+    #[typedef(rename = "FOO")]
+    Foo,
+    #[serde(rename = "bar-bar")]
+    BarBar,
+    BazBaz,
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum RenameStructVariants {
+    #[typedef(rename = "bar")]
+    Bar {
+        x: u32,
+    },
+    Baz {
+        y: String,
+    },
+}
+
+#[test]
+fn rename_variants() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<RenameVariants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "enum": ["FOO", "bar-bar", "baz_baz"],
+        }}
+    );
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<RenameStructVariants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "bar": {
+                    "properties": {
+                        "x": { "type": "uint32" }
+                    },
+                    "additionalProperties": true
+                },
+                "Baz": {
+                    "properties": {
+                        "y": { "type": "string" }
+                    },
+                    "additionalProperties": true
+                }
+            }
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct Pagination {
+    page: u32,
+    #[serde(default)]
+    per_page: u32,
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct Flattened {
+    query: String,
+    #[serde(flatten)]
+    pagination: Pagination,
+    #[serde(flatten)]
+    extra: Option<Extra>,
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct Extra {
+    verbose: bool,
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[allow(dead_code)]
+struct FlattenedMap {
+    query: String,
+    #[serde(flatten)]
+    rest: std::collections::HashMap<String, String>,
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct FlattenedPrimitive {
+    #[serde(flatten)]
+    x: u32,
+}
+
+#[test]
+fn flatten() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<Flattened>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "query": { "type": "string" },
+                "page": { "type": "uint32" },
+            },
+            "optionalProperties": {
+                "per_page": { "type": "uint32" },
+                "verbose": { "type": "boolean" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+
+    // the map captures whatever else is there
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<FlattenedMap>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "query": { "type": "string" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+
+    assert_eq!(
+        Generator::default()
+            .into_root_schema::<FlattenedPrimitive>()
+            .unwrap_err(),
+        GenError::InvalidFlatten {
+            type_name: "uint32".to_string()
+        }
+    );
+}
+
+mod from_string {
+    use serde::{Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<u32, D::Error> {
+        String::deserialize(d)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+struct CustomDeserializer {
+    #[serde(deserialize_with = "from_string::deserialize")]
+    #[typedef(as = "String")]
+    x: u32,
+}
+
+#[test]
+fn serde_with() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<CustomDeserializer>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": { "type": "string" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(rename_all(serialize = "camelCase"))]
+#[allow(dead_code)]
+struct DirectionalRenames {
+    foo_bar: bool,
+    #[serde(rename(serialize = "out", deserialize = "in"))]
+    both: u32,
+    kind: DirectionalVariants,
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[typedef(discriminants)]
+#[allow(dead_code)]
+enum DirectionalVariants {
+    #[serde(rename(serialize = "a_out"))]
+    A = 1,
+    B = 2,
+}
+
+#[test]
+fn directional_renames() {
+    let schema = |mode| {
+        serde_json::to_value(
+            Generator::builder()
+                .mode(mode)
+                .prefer_inline()
+                .build()
+                .into_root_schema::<DirectionalRenames>()
+                .unwrap(),
+        )
+        .unwrap()
+    };
+
+    assert_eq!(
+        schema(SchemaMode::Deserialize),
+        serde_json::json! {{
+            "properties": {
+                "foo_bar": { "type": "boolean" },
+                "in": { "type": "uint32" },
+                "kind": {
+                    "metadata": { "discriminants": { "A": 1, "B": 2 } },
+                    "enum": ["A", "B"]
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+    assert_eq!(
+        schema(SchemaMode::Serialize),
+        serde_json::json! {{
+            "properties": {
+                "fooBar": { "type": "boolean" },
+                "out": { "type": "uint32" },
+                "kind": {
+                    "metadata": { "discriminants": { "a_out": 1, "B": 2 } },
+                    "enum": ["a_out", "B"]
+                },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[cfg_attr(test, serde(rename_all = "camelCase"))]
+#[allow(dead_code)]
+struct CfgFields {
+    always_there: u32,
+    #[cfg(test)]
+    enabled: u32,
+    #[cfg(not(test))]
+    disabled: u32,
+    #[cfg_attr(test, serde(rename = "renamed"))]
+    #[cfg_attr(not(test), serde(rename = "not_renamed"))]
+    maybe_renamed: u32,
+    #[cfg_attr(test, typedef(nullable))]
+    #[cfg_attr(not(test), typedef(metadata(ignored = true)))]
+    maybe_nullable: u32,
+}
+
+#[test]
+fn cfg_fields() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<CfgFields>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "alwaysThere": { "type": "uint32" },
+                "enabled": { "type": "uint32" },
+                "renamed": { "type": "uint32" },
+                "maybeNullable": { "type": "uint32", "nullable": true }
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[allow(dead_code)]
+enum CfgVariants {
+    Foo,
+    #[cfg(not(test))]
+    Bar,
+    #[cfg_attr(test, serde(rename = "baz"))]
+    Baz,
+}
+
+#[test]
+fn cfg_variants() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<CfgVariants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "enum": ["Foo", "baz"]
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(dead_code)]
+enum UnitOther {
+    Foo,
+    Bar,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(tag = "type")]
+#[allow(dead_code)]
+enum StructOther {
+    Foo {
+        x: u32,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[test]
+fn serde_other() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<UnitOther>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "metadata": { "catchAll": "unknown" },
+            "enum": ["foo", "bar", "unknown"]
+        }}
+    );
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<StructOther>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "metadata": { "catchAll": "Unknown" },
+            "discriminator": "type",
+            "mapping": {
+                "Foo": {
+                    "properties": { "x": { "type": "uint32" } },
+                    "additionalProperties": true
+                },
+                "Unknown": {
+                    "additionalProperties": true
+                }
+            }
+        }}
+    );
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(rename = "user")]
+#[typedef(serde_name)]
+#[allow(dead_code)]
+struct RenamedUser {
+    name: String,
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(rename = "role")]
+#[allow(dead_code)]
+struct RenamedRole {
+    name: String,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(rename = "group")]
+#[allow(dead_code)]
+struct RenamedGroup {
+    members: Vec<RenamedUser>,
+    roles: Vec<RenamedRole>,
+}
+
+#[test]
+fn container_rename() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<RenamedGroup>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "definitions": {
+                "serde::user": {
+                    "properties": {
+                        "name": { "type": "string" }
+                    },
+                    "additionalProperties": true
+                },
+                "serde::RenamedRole": {
+                    "properties": {
+                        "name": { "type": "string" }
+                    },
+                    "additionalProperties": true
+                }
+            },
+            "properties": {
+                "members": {
+                    "elements": { "ref": "serde::user" }
+                },
+                "roles": {
+                    "elements": { "ref": "serde::RenamedRole" }
+                }
+            },
+            "additionalProperties": true
+        }}
+    );
+    assert_eq!(RenamedGroup::names().short, "group");
+}
+
+#[derive(JsonTypedef, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[typedef(additional_properties = true)]
+#[allow(dead_code)]
+struct ExplicitlyOpen {
+    x: u32,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(additional_properties = false)]
+#[allow(dead_code)]
+struct ExplicitlyClosed {
+    x: u32,
+}
+
+#[derive(JsonTypedef)]
+#[typedef(tag = "type")]
+#[allow(dead_code)]
+enum ExplicitVariants {
+    #[typedef(additional_properties = false)]
+    Closed {
+        x: u32,
+    },
+    Open {
+        x: u32,
+    },
+}
+
+#[test]
+fn explicit_additional_properties() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<ExplicitlyOpen>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": { "x": { "type": "uint32" } },
+            "additionalProperties": true
+        }}
+    );
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<ExplicitlyClosed>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": { "x": { "type": "uint32" } }
+        }}
+    );
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<ExplicitVariants>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "discriminator": "type",
+            "mapping": {
+                "Closed": {
+                    "properties": { "x": { "type": "uint32" } }
+                },
+                "Open": {
+                    "properties": { "x": { "type": "uint32" } },
+                    "additionalProperties": true
+                }
+            }
+        }}
+    );
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "serde_valid")]
+
+use jtd_derive::{Generator, JsonTypedef};
+
+const MAX: u32 = 100;
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Foo {
+    #[validate(minimum = 1, maximum = MAX)]
+    #[validate(multiple_of = 2)]
+    x: u32,
+    #[validate(min_length = 1, max_length = 10, pattern = r"^\w+$")]
+    #[typedef(metadata(description = "the name"))]
+    name: String,
+    #[validate(min_items = 1, unique_items)]
+    #[validate(custom = |_| Ok(()))]
+    tags: Vec<String>,
+    #[validate(enumerate = [-1.5, 2.5])]
+    ratio: f32,
+    #[validate(enumerate(1, 2))]
+    legacy: u8,
+    #[validate]
+    bar: Bar,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Bar(#[validate(exclusive_minimum = -5)] i8);
+
+#[test]
+fn constraints_become_metadata() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::builder()
+                .prefer_inline()
+                .build()
+                .into_root_schema::<Foo>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": {
+                    "metadata": { "minimum": 1, "maximum": 100, "multipleOf": 2 },
+                    "type": "uint32",
+                },
+                "name": {
+                    "metadata": {
+                        "description": "the name",
+                        "minLength": 1,
+                        "maxLength": 10,
+                        "pattern": "^\\w+$",
+                    },
+                    "type": "string",
+                },
+                "tags": {
+                    "metadata": { "minItems": 1, "uniqueItems": true },
+                    "elements": { "type": "string" },
+                },
+                "ratio": {
+                    "metadata": { "enum": [-1.5, 2.5] },
+                    "type": "float32",
+                },
+                "legacy": {
+                    "metadata": { "enum": [1, 2] },
+                    "type": "uint8",
+                },
+                "bar": {
+                    "metadata": { "exclusiveMinimum": -5 },
+                    "type": "int8",
+                },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "serde_with")]
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use jtd_derive::{Generator, JsonTypedef};
+use serde::Serialize;
+use serde_with::{Base64, BoolFromInt, DisplayFromStr, DurationSeconds, Same};
+
+/// Stands in for the `serde_with` crate. The derive only looks at the
+/// attributes `#[serde_as]` generates.
+mod serde_with {
+    use std::marker::PhantomData;
+
+    pub struct As<T: ?Sized>(PhantomData<T>);
+
+    impl<T: ?Sized> As<T> {
+        pub fn serialize<V: serde::Serialize, S: serde::Serializer>(
+            value: &V,
+            s: S,
+        ) -> Result<S::Ok, S::Error> {
+            value.serialize(s)
+        }
+    }
+
+    pub struct Same;
+    pub struct DisplayFromStr;
+    pub struct Base64;
+    pub struct BoolFromInt;
+    pub struct DurationSeconds<F = u64>(PhantomData<F>);
+}
+
+#[derive(JsonTypedef, Serialize)]
+#[allow(dead_code)]
+struct Adapted {
+    #[serde(with = "serde_with::As::<DisplayFromStr>")]
+    id: u32,
+    #[serde(with = "serde_with::As::<Vec<DisplayFromStr>>")]
+    ids: Vec<u32>,
+    #[serde(with = "serde_with::As::<BTreeMap<DisplayFromStr, Same>>")]
+    counts: BTreeMap<u8, u32>,
+    #[serde(with = "serde_with::As::<Option<Base64>>")]
+    data: Option<Vec<u8>>,
+    #[serde(with = "serde_with::As::<[BoolFromInt; 2]>")]
+    flags: [bool; 2],
+    #[serde(with = "serde_with::As::<DurationSeconds>")]
+    timeout: Duration,
+    #[serde(with = "serde_with::As::<DurationSeconds<String>>")]
+    interval: Duration,
+}
+
+#[test]
+fn adapters() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Adapted>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "id": { "type": "string" },
+                "ids": { "elements": { "type": "string" } },
+                "counts": { "values": { "type": "uint32" } },
+                "data": {
+                    "metadata": { "contentEncoding": "base64" },
+                    "type": "string",
+                    "nullable": true
+                },
+                "flags": { "elements": { "type": "uint8" } },
+                "timeout": { "type": "float64" },
+                "interval": { "type": "string" },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
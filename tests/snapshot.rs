@@ -0,0 +1,65 @@
+#![cfg(feature = "testing")]
+
+use jtd_derive::{assert_schema_snapshot, Generator, JsonTypedef};
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Foo {
+    x: u32,
+    bar: Bar,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Bar {
+    y: Option<String>,
+}
+
+#[test]
+fn matching_snapshot() {
+    assert_schema_snapshot!(Foo, "tests/snapshots/foo.json");
+    assert_schema_snapshot!(
+        Foo,
+        "tests/snapshots/foo_short.json",
+        Generator::builder().naming_short().build(),
+    );
+}
+
+struct NotJsonTypedef;
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Generic<T, U> {
+    x: T,
+    marker: std::marker::PhantomData<U>,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Instantiations {
+    a: Generic<u32, NotJsonTypedef>,
+    b: Generic<u32, Option<NotJsonTypedef>>,
+}
+
+// `U` isn't bound by `JsonTypedef`, so its name comes from `type_name`, which
+// may change between compiler versions
+#[test]
+fn unbound_type_param_names() {
+    assert_schema_snapshot!(
+        Instantiations,
+        "tests/snapshots/unbound_type_params.json",
+        Generator::builder().top_level_ref().build(),
+    );
+}
+
+#[test]
+#[should_panic(expected = "schema doesn't match snapshot")]
+fn mismatching_snapshot() {
+    assert_schema_snapshot!(Bar, "tests/snapshots/foo.json");
+}
+
+#[test]
+#[should_panic(expected = "failed to read snapshot")]
+fn missing_snapshot() {
+    assert_schema_snapshot!(Bar, "tests/snapshots/missing.json");
+}
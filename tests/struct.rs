@@ -1,3 +1,4 @@
+use jtd_derive::schema::{Schema, TypeSchema};
 use jtd_derive::{Generator, JsonTypedef};
 
 #[derive(JsonTypedef)]
@@ -84,3 +85,335 @@ fn nested() {
         }}
     );
 }
+
+#[test]
+fn skipped_fields() {
+    struct NotJsonTypedef;
+
+    #[derive(JsonTypedef, serde::Serialize)]
+    #[allow(unused)]
+    struct Foo {
+        x: u32,
+        #[typedef(skip)]
+        #[serde(skip)]
+        cache: NotJsonTypedef,
+        #[serde(skip)]
+        internal: Vec<u8>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Foo>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": { "type": "uint32" },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn phantom_data_fields() {
+    struct NotJsonTypedef;
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo {
+        x: u32,
+        marker: std::marker::PhantomData<NotJsonTypedef>,
+        #[typedef(as = "u32")]
+        explicit: std::marker::PhantomData<NotJsonTypedef>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Foo>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": { "type": "uint32" },
+                "explicit": { "type": "uint32" },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[test]
+fn inferred_bounds() {
+    struct NotJsonTypedef;
+
+    #[derive(JsonTypedef)]
+    #[allow(unused)]
+    struct Foo<T, U, V> {
+        x: Vec<T>,
+        marker: std::marker::PhantomData<U>,
+        #[typedef(skip)]
+        cache: Option<V>,
+    }
+
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<Foo<u32, NotJsonTypedef, NotJsonTypedef>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": { "elements": { "type": "uint32" } },
+            },
+            "additionalProperties": true
+        }}
+    );
+
+    // instantiations are still told apart by all their parameters
+    assert_ne!(
+        Foo::<u32, NotJsonTypedef, u8>::names(),
+        Foo::<u32, u8, u8>::names()
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Bounded<T: Clone + Default = u32, U = String>
+where
+    U: std::fmt::Debug,
+{
+    x: T,
+    y: Option<U>,
+}
+
+#[test]
+fn existing_bounds_and_defaults() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Bounded>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": { "type": "uint32" },
+                "y": { "type": "string", "nullable": true },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Defaults<'a, T = u32, const N: usize = 2> {
+    x: [T; N],
+    y: &'a str,
+}
+
+#[test]
+fn generic_defaults() {
+    assert_eq!(format!("{:#}", <Defaults>::names()), "Defaults<uint32, 2>");
+    assert_eq!(
+        format!("{:#}", Defaults::<bool, 3>::names()),
+        "Defaults<boolean, 3>"
+    );
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Defaults>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": { "elements": { "type": "uint32" } },
+                "y": { "type": "string" },
+            },
+            "additionalProperties": true
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Inner {
+    x: u32,
+    y: Option<String>,
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Outer {
+    z: bool,
+    #[typedef(flatten)]
+    inner: Inner,
+}
+
+#[test]
+fn flatten() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Outer>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": { "type": "uint32" },
+                "y": { "type": "string", "nullable": true },
+                "z": { "type": "boolean" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[allow(dead_code)]
+struct NotTypedef(u64);
+
+fn timestamp_schema(_gen: &mut Generator) -> Schema {
+    Schema::of_type(TypeSchema::Timestamp)
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct SchemaWith {
+    #[typedef(schema_with = "timestamp_schema")]
+    created: NotTypedef,
+}
+
+#[test]
+fn schema_with() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<SchemaWith>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "created": { "type": "timestamp" },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct SchemaAs {
+    #[typedef(as = "String")]
+    id: u32,
+    #[typedef(as = "Option<Vec<String>>")]
+    tags: NotTypedef,
+}
+
+#[test]
+fn schema_as() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<SchemaAs>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "id": { "type": "string" },
+                "tags": { "elements": { "type": "string" }, "nullable": true },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct NullableFields {
+    #[typedef(nullable)]
+    x: u32,
+    #[typedef(nullable)]
+    y: Option<u32>,
+}
+
+#[test]
+fn nullable_fields() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<NullableFields>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": { "type": "uint32", "nullable": true },
+                "y": { "type": "uint32", "nullable": true },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct EnumValues {
+    #[typedef(enum_values("created", "deleted"))]
+    status: String,
+    #[typedef(enum_values("low", "high"))]
+    priority: Option<String>,
+}
+
+#[test]
+fn enum_values() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<EnumValues>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "status": { "enum": ["created", "deleted"] },
+                "priority": { "enum": ["low", "high"], "nullable": true },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
+
+/// (De)serialized as `{ "name": count, ... }` by hand.
+#[derive(JsonTypedef)]
+#[typedef(values = "u32")]
+#[allow(dead_code)]
+struct Counts {
+    names: Vec<String>,
+    counts: Vec<u32>,
+}
+
+#[test]
+fn values() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Counts>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "values": { "type": "uint32" }
+        }}
+    );
+}
+
+struct Email(#[allow(dead_code)] String);
+struct Meters(#[allow(dead_code)] f32);
+struct Tags<T>(#[allow(dead_code)] Vec<T>);
+
+jtd_derive::impl_json_typedef!(Email => string);
+jtd_derive::impl_json_typedef!(Meters => transparent(f32));
+jtd_derive::impl_json_typedef!(impl<T> Tags<T> => elements(T));
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct ManualImpls {
+    email: Email,
+    distance: Meters,
+    tags: Tags<Email>,
+}
+
+#[test]
+fn impl_json_typedef() {
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<ManualImpls>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "email": { "type": "string" },
+                "distance": { "type": "float32" },
+                "tags": { "elements": { "type": "string" } },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}
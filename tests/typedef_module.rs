@@ -0,0 +1,52 @@
+use jtd_derive::registry::SchemaRegistry;
+use jtd_derive::{Generator, JsonTypedef};
+
+#[jtd_derive::typedef_module]
+#[allow(dead_code)]
+mod api {
+    use jtd_derive::JsonTypedef;
+
+    pub struct Request {
+        pub id: u32,
+        pub page: Page<u8>,
+    }
+
+    #[derive(JsonTypedef)]
+    #[typedef(tag = "kind")]
+    pub enum Event {
+        Created { id: u32 },
+    }
+
+    pub struct Page<T> {
+        pub items: Vec<T>,
+    }
+
+    #[typedef(skip)]
+    pub struct NotExported(std::cell::Cell<fn()>);
+}
+
+#[test]
+fn derives_and_registers() {
+    let mut registry = SchemaRegistry::new();
+    api::register_schemas(&mut registry).unwrap();
+
+    assert_eq!(
+        registry.names().collect::<Vec<_>>(),
+        ["typedef_module::api::Event", "typedef_module::api::Request"]
+    );
+    assert_eq!(
+        serde_json::to_value(
+            Generator::default()
+                .into_root_schema::<api::Page<u32>>()
+                .unwrap()
+        )
+        .unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "items": { "elements": { "type": "uint32" } }
+            },
+            "additionalProperties": true
+        }}
+    );
+    assert_eq!(api::Request::names().short, "Request");
+}
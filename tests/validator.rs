@@ -0,0 +1,73 @@
+#![cfg(feature = "validator")]
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use jtd_derive::{Generator, JsonTypedef};
+
+const MAX: u32 = 100;
+
+static NAME: LazyLock<Regex> = LazyLock::new(|| Regex(r"^\w+$"));
+
+/// Stands in for `regex::Regex`.
+struct Regex(&'static str);
+
+impl Regex {
+    fn as_str(&self) -> &str {
+        self.0
+    }
+}
+
+#[derive(JsonTypedef)]
+#[allow(dead_code)]
+struct Foo {
+    #[validate(range(min = 1, max = MAX))]
+    x: u32,
+    #[validate(length(min = 1, max = 10), regex(path = *NAME))]
+    name: String,
+    #[validate(email)]
+    email: Option<String>,
+    #[validate(url)]
+    homepage: String,
+    #[validate(length(equal = 2))]
+    tags: Vec<String>,
+    #[validate(length(min = 1), custom(function = "check"))]
+    counts: HashMap<String, u32>,
+}
+
+#[test]
+fn constraints_become_metadata() {
+    assert_eq!(
+        serde_json::to_value(Generator::default().into_root_schema::<Foo>().unwrap()).unwrap(),
+        serde_json::json! {{
+            "properties": {
+                "x": {
+                    "metadata": { "minimum": 1, "maximum": 100 },
+                    "type": "uint32",
+                },
+                "name": {
+                    "metadata": { "minLength": 1, "maxLength": 10, "pattern": r"^\w+$" },
+                    "type": "string",
+                },
+                "email": {
+                    "metadata": { "format": "email" },
+                    "type": "string",
+                    "nullable": true,
+                },
+                "homepage": {
+                    "metadata": { "format": "uri" },
+                    "type": "string",
+                },
+                "tags": {
+                    "metadata": { "minItems": 2, "maxItems": 2 },
+                    "elements": { "type": "string" },
+                },
+                "counts": {
+                    "metadata": { "minProperties": 1 },
+                    "values": { "type": "uint32" },
+                },
+            },
+            "additionalProperties": true,
+        }}
+    );
+}